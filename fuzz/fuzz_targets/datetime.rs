@@ -0,0 +1,35 @@
+//! Decodes a `DateTime` from a string and from signed and unsigned integers.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::de::{
+    value::{Error, I64Deserializer, StrDeserializer, U64Deserializer},
+    Deserialize,
+};
+
+use github_types::DateTime;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() == 8 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(data);
+
+        let signed = i64::from_le_bytes(bytes);
+        if let Ok(datetime) =
+            DateTime::deserialize(I64Deserializer::<Error>::new(signed))
+        {
+            assert_eq!(datetime.timestamp(), signed);
+        }
+
+        let unsigned = u64::from_le_bytes(bytes);
+        if let Ok(datetime) =
+            DateTime::deserialize(U64Deserializer::<Error>::new(unsigned))
+        {
+            assert_eq!(datetime.timestamp() as u64, unsigned);
+        }
+    }
+
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = DateTime::deserialize(StrDeserializer::<Error>::new(s));
+    }
+});
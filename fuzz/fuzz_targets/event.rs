@@ -0,0 +1,46 @@
+//! Decodes a webhook the same way a server would.
+//!
+//! The first line of the input is the `X-GitHub-Event` header and the rest is
+//! the body. The bundled fixtures make a good seed corpus, e.g.:
+//!
+//! ```sh
+//! mkdir -p corpus/event
+//! for f in ../fixtures/*.json ../fixtures/*/*.json; do
+//!     e=$(basename $(dirname $f)); [ "$e" = fixtures ] && e=$(basename $f .json)
+//!     (echo $e; cat $f) > corpus/event/$(echo $f | tr / _)
+//! done
+//! ```
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use github_types::{Event, EventType};
+
+fuzz_target!(|data: &[u8]| {
+    let data = match std::str::from_utf8(data) {
+        Ok(data) => data,
+        Err(_) => return,
+    };
+
+    let (header, body) = match data.find('\n') {
+        Some(i) => (&data[..i], &data[i + 1..]),
+        None => (data, ""),
+    };
+
+    let event_type: EventType = match header.parse() {
+        Ok(event_type) => event_type,
+        Err(_) => return,
+    };
+
+    if let Ok(event) = Event::from_json(event_type, body) {
+        assert_eq!(event.event_type(), event_type);
+
+        // Anything that was decoded must survive a round-trip.
+        let mut json = Vec::new();
+        event
+            .serialize_payload(&mut serde_json::Serializer::new(&mut json))
+            .unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert_eq!(Event::from_json(event_type, &json).unwrap(), event);
+    }
+});
@@ -0,0 +1,22 @@
+//! Decodes an `Oid` from both a hex string and raw bytes.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::de::{
+    value::{BytesDeserializer, Error, StrDeserializer},
+    Deserialize,
+};
+
+use github_types::Oid;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(oid) = Oid::deserialize(BytesDeserializer::<Error>::new(data)) {
+        assert_eq!(&oid[..], data);
+    }
+
+    if let Ok(s) = std::str::from_utf8(data) {
+        if let Ok(oid) = Oid::deserialize(StrDeserializer::<Error>::new(s)) {
+            assert_eq!(oid.to_string(), s.to_lowercase());
+        }
+    }
+});
@@ -18,20 +18,31 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{DateTime, EventType, User};
+use crate::{AppId, DateTime, EventType, InstallationId, User, UserId};
 
 pub trait AppEvent {
     /// Returns the installation ID for the event.
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         None
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Permission {
     Read,
@@ -39,7 +50,11 @@ pub enum Permission {
 }
 
 /// Permissions given to the app installation.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InstallationPermissions {
     pub issues: Option<Permission>,
     pub contents: Option<Permission>,
@@ -48,16 +63,20 @@ pub struct InstallationPermissions {
 }
 
 /// Information about an app installation.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Installation {
-    pub id: u64,
+    pub id: InstallationId,
     pub account: User,
     pub repository_selection: String,
     pub access_tokens_url: String,
     pub repositories_url: String,
     pub html_url: String,
-    pub app_id: u64,
-    pub target_id: u64,
+    pub app_id: AppId,
+    pub target_id: UserId,
     pub target_type: String,
     pub permissions: InstallationPermissions,
     pub events: Vec<EventType>,
@@ -67,9 +86,13 @@ pub struct Installation {
 }
 
 /// Information about an app.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct App {
-    pub id: u64,
+    pub id: AppId,
     pub owner: User,
     pub name: String,
     pub description: String,
@@ -0,0 +1,344 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! `proptest` strategies for types that can't derive `Arbitrary`.
+//!
+//! Most types derive `Arbitrary` directly when the `arbitrary` feature is
+//! enabled. The types implemented here either wrap a foreign type, default to
+//! arbitrary JSON, or are too large to derive.
+
+use chrono::{FixedOffset, TimeZone};
+use proptest::prelude::*;
+use serde_json::Value;
+
+use crate::{
+    CheckRunEvent, CheckSuiteEvent, ClientPayload, CommitCommentEvent,
+    CreateEvent, DateTime, DeleteEvent, DeployKeyEvent, Event,
+    GitHubAppAuthorizationEvent, GollumEvent, InstallationEvent,
+    InstallationRepositoriesEvent, IntegrationInstallationEvent,
+    IntegrationInstallationRepositoriesEvent, IssueCommentEvent, IssuesEvent,
+    LabelEvent, MetaEvent, Oid, PackageEvent, PingEvent, PullRequestEvent,
+    PullRequestReviewCommentEvent, PullRequestReviewEvent,
+    PullRequestReviewThreadEvent, PushEvent, RegistryPackageEvent, Repository,
+    RepositoryDispatch, RepositoryDispatchEvent, RepositoryEvent,
+    RepositoryRulesetEvent, ShortInstallation, StarEvent, User, WatchEvent,
+    WorkflowDispatch,
+};
+
+/// The latest timestamp that can be represented in RFC 3339, which only
+/// allows four digit years (i.e., `9999-12-31T23:59:59Z`).
+const MAX_RFC3339: i64 = 253_402_300_799;
+
+/// The earliest timestamp that can be represented in RFC 3339 (i.e.,
+/// `0000-01-01T00:00:00Z`).
+const MIN_RFC3339: i64 = -62_167_219_200;
+
+impl Arbitrary for Oid {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            1 => Just(Oid::ZERO),
+            1 => Just(Oid::EMPTY_TREE),
+            8 => any::<[u8; 20]>()
+                .prop_map(|bytes| Oid::from_hex(&hex::encode(bytes)).unwrap()),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for DateTime {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let min = chrono::DateTime::<chrono::Utc>::MIN_UTC.timestamp();
+        let max = chrono::DateTime::<chrono::Utc>::MAX_UTC.timestamp();
+
+        // Only whole seconds are generated since binary formats serialize
+        // the timestamp as an integer. The whole range that can be
+        // deserialized is covered, including years that RFC 3339 can't
+        // represent.
+        let secs = prop_oneof![
+            1 => Just(0),
+            1 => Just(min),
+            1 => Just(max),
+            1 => Just(MIN_RFC3339),
+            1 => Just(MIN_RFC3339 - 1),
+            1 => Just(MAX_RFC3339),
+            1 => Just(MAX_RFC3339 + 1),
+            8 => MIN_RFC3339..=MAX_RFC3339,
+            4 => min..=max,
+        ];
+
+        // Either form, with any UTC offset that RFC 3339 can represent
+        // (i.e., whole minutes).
+        let offset = prop_oneof![
+            1 => Just(0),
+            1 => (-1439..=1439i32).prop_map(|minutes| minutes * 60),
+        ];
+
+        (secs, proptest::option::of(offset))
+            .prop_map(|(secs, offset)| match offset {
+                Some(offset) => DateTime::from(
+                    chrono::Utc
+                        .timestamp_opt(secs, 0)
+                        .unwrap()
+                        .with_timezone(&FixedOffset::east_opt(offset).unwrap()),
+                ),
+                None => DateTime::from_timestamp(secs).unwrap(),
+            })
+            .boxed()
+    }
+}
+
+/// Generates arbitrary JSON without floating point numbers, which don't
+/// survive a round-trip through every format.
+pub fn json_value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        any::<u64>().prop_map(Value::from),
+        any::<String>().prop_map(Value::from),
+    ];
+
+    leaf.prop_recursive(3, 32, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+            json_object(inner).prop_map(Value::Object),
+        ]
+    })
+}
+
+fn json_object<S>(
+    values: S,
+) -> impl Strategy<Value = serde_json::Map<String, Value>>
+where
+    S: Strategy<Value = Value>,
+{
+    prop::collection::vec((any::<String>(), values), 0..8)
+        .prop_map(|entries| entries.into_iter().collect())
+}
+
+impl Arbitrary for ClientPayload {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        json_value().prop_map(ClientPayload).boxed()
+    }
+}
+
+impl Arbitrary for RepositoryDispatch {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), json_value())
+            .prop_map(|(event_type, client_payload)| {
+                RepositoryDispatch::new(
+                    event_type,
+                    ClientPayload(client_payload),
+                )
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for WorkflowDispatch {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), prop::option::of(json_object(json_value())))
+            .prop_map(|(git_ref, inputs)| WorkflowDispatch { git_ref, inputs })
+            .boxed()
+    }
+}
+
+impl Arbitrary for RepositoryDispatchEvent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            json_value(),
+            any::<Repository>(),
+            any::<User>(),
+            any::<Option<ShortInstallation>>(),
+        )
+            .prop_map(
+                |(
+                    action,
+                    branch,
+                    client_payload,
+                    repository,
+                    sender,
+                    installation,
+                )| {
+                    RepositoryDispatchEvent {
+                        action,
+                        branch,
+                        client_payload: ClientPayload(client_payload),
+                        repository,
+                        sender,
+                        installation,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Event {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        // Deriving this would store the value trees of every variant inline,
+        // which is large enough to overflow the stack. Boxing each variant
+        // avoids that.
+        fn variant<T>() -> BoxedStrategy<Event>
+        where
+            T: Arbitrary + Into<Event> + 'static,
+        {
+            any::<T>().prop_map(Into::into).boxed()
+        }
+
+        prop::strategy::Union::new(vec![
+            variant::<PingEvent>(),
+            variant::<CheckRunEvent>(),
+            variant::<CheckSuiteEvent>(),
+            variant::<CommitCommentEvent>(),
+            variant::<CreateEvent>(),
+            variant::<DeleteEvent>(),
+            variant::<DeployKeyEvent>(),
+            variant::<GitHubAppAuthorizationEvent>(),
+            variant::<GollumEvent>(),
+            variant::<InstallationEvent>(),
+            variant::<InstallationRepositoriesEvent>(),
+            variant::<IntegrationInstallationEvent>(),
+            variant::<IntegrationInstallationRepositoriesEvent>(),
+            variant::<IssueCommentEvent>(),
+            variant::<IssuesEvent>(),
+            variant::<LabelEvent>(),
+            variant::<MetaEvent>(),
+            variant::<PackageEvent>(),
+            variant::<PullRequestEvent>(),
+            variant::<PullRequestReviewEvent>(),
+            variant::<PullRequestReviewCommentEvent>(),
+            variant::<PullRequestReviewThreadEvent>(),
+            variant::<PushEvent>(),
+            variant::<RegistryPackageEvent>(),
+            variant::<RepositoryEvent>(),
+            variant::<RepositoryDispatchEvent>(),
+            variant::<RepositoryRulesetEvent>(),
+            variant::<StarEvent>(),
+            variant::<WatchEvent>(),
+        ])
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::fmt::Debug;
+
+    use crate::{AuthenticatedUser, Deployment, NewCheckRun, UpdateCheckRun};
+
+    /// Events are large, so fewer cases are run than the default to keep the
+    /// tests reasonably fast.
+    fn config() -> ProptestConfig {
+        ProptestConfig::with_cases(64)
+    }
+
+    fn json_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let json = serde_json::to_string(value)?;
+        prop_assert_eq!(&serde_json::from_str::<T>(&json)?, value);
+        Ok(())
+    }
+
+    fn msgpack_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let bytes = rmp_serde::to_vec_named(value)?;
+        prop_assert_eq!(&rmp_serde::from_slice::<T>(&bytes)?, value);
+        Ok(())
+    }
+
+    macro_rules! round_trip {
+        ($($name:ident: $type:ty,)*) => {
+            $(
+                mod $name {
+                    use super::*;
+
+                    proptest! {
+                        #![proptest_config(config())]
+
+                        #[test]
+                        fn json(value: $type) {
+                            json_round_trip(&value)?;
+                        }
+
+                        #[test]
+                        fn msgpack(value: $type) {
+                            msgpack_round_trip(&value)?;
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    round_trip! {
+        event: Event,
+        authenticated_user: AuthenticatedUser,
+        new_check_run: NewCheckRun,
+        update_check_run: UpdateCheckRun,
+        repository_dispatch: RepositoryDispatch,
+        workflow_dispatch: WorkflowDispatch,
+        deployment: Deployment,
+    }
+
+    proptest! {
+        #![proptest_config(config())]
+
+        #[test]
+        fn event_payload_round_trip(event: Event) {
+            let mut json = Vec::new();
+            let mut serializer = serde_json::Serializer::new(&mut json);
+            event.serialize_payload(&mut serializer)?;
+            let json = String::from_utf8(json)?;
+            let parsed = Event::from_json(event.event_type(), &json)?;
+            prop_assert_eq!(parsed, event);
+        }
+    }
+}
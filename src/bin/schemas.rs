@@ -0,0 +1,36 @@
+//! Writes a JSON Schema for the payload of every supported webhook event.
+//!
+//! Each schema is written to `<dir>/<event>.json`, where `<event>` is the
+//! value of the `X-GitHub-Event` header (e.g., `pull_request.json`):
+//!
+//! ```sh
+//! cargo run --features schemars --bin github-types-schemas -- schemas
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+
+use github_types::schema;
+
+fn main() -> io::Result<()> {
+    let dir = match env::args_os().nth(1) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            eprintln!("Usage: github-types-schemas <dir>");
+            process::exit(2);
+        }
+    };
+
+    fs::create_dir_all(&dir)?;
+
+    for (event_type, schema) in schema::payloads() {
+        let mut json = serde_json::to_string_pretty(&schema)?;
+        json.push('\n');
+        fs::write(dir.join(format!("{}.json", event_type)), json)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,570 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Conversion of cargo and rustc JSON diagnostics into check run annotations.
+//!
+//! This reads the output of `cargo build --message-format=json` (or `cargo
+//! clippy`, `cargo check`, etc.) and turns each compiler message into an
+//! `Annotation` that can be attached to a check run.
+
+use serde::Deserialize;
+
+use std::collections::HashSet;
+use std::io::{self, BufRead};
+use std::path::{Component, Path};
+
+use crate::check_run_request::truncate_annotation_text;
+use crate::{Annotation, AnnotationLevel};
+
+/// A single line of output from `cargo --message-format=json`.
+///
+/// Only the messages relevant to annotations are modelled. All others are
+/// deserialized as `Message::Other`.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum Message {
+    /// A diagnostic emitted by the compiler.
+    CompilerMessage {
+        /// The package that was being compiled.
+        package_id: String,
+
+        /// The diagnostic itself.
+        message: Diagnostic,
+    },
+
+    /// The final message of a build.
+    BuildFinished {
+        /// Whether or not the build succeeded.
+        success: bool,
+    },
+
+    /// Any other message (e.g., `compiler-artifact` or
+    /// `build-script-executed`).
+    #[serde(other)]
+    Other,
+}
+
+/// The severity of a diagnostic.
+#[derive(
+    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticLevel {
+    #[serde(rename = "error: internal compiler error")]
+    Ice,
+    Error,
+    Warning,
+    Note,
+    Help,
+    FailureNote,
+
+    /// A level added to `rustc` after this was written. These are treated as
+    /// failures so that they aren't overlooked.
+    #[serde(other)]
+    Other,
+}
+
+impl From<DiagnosticLevel> for AnnotationLevel {
+    fn from(level: DiagnosticLevel) -> Self {
+        match level {
+            DiagnosticLevel::Ice
+            | DiagnosticLevel::Error
+            | DiagnosticLevel::Other => AnnotationLevel::Failure,
+            DiagnosticLevel::Warning => AnnotationLevel::Warning,
+            DiagnosticLevel::Note
+            | DiagnosticLevel::Help
+            | DiagnosticLevel::FailureNote => AnnotationLevel::Notice,
+        }
+    }
+}
+
+/// The error code or lint name of a diagnostic.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DiagnosticCode {
+    /// The code (e.g., `E0308` or `clippy::needless_return`).
+    pub code: String,
+
+    /// An explanation of the error code, if there is one.
+    pub explanation: Option<String>,
+}
+
+/// A region of source code that a diagnostic refers to.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DiagnosticSpan {
+    /// The path to the file. This is relative to the directory `rustc` was
+    /// invoked from (usually the workspace root), or absolute for files
+    /// outside of the workspace.
+    pub file_name: String,
+    pub byte_start: u32,
+    pub byte_end: u32,
+
+    /// 1-based line number where the span starts.
+    pub line_start: u32,
+
+    /// 1-based line number where the span ends (inclusive).
+    pub line_end: u32,
+
+    /// 1-based character column where the span starts.
+    pub column_start: u32,
+
+    /// 1-based character column where the span ends (exclusive).
+    pub column_end: u32,
+
+    /// Whether this is the main location of the diagnostic.
+    pub is_primary: bool,
+
+    /// A label for this span, if any.
+    pub label: Option<String>,
+
+    /// Suggested replacement text for this span, if any.
+    pub suggested_replacement: Option<String>,
+
+    /// If this span is inside a macro expansion, this describes where the
+    /// macro was invoked.
+    pub expansion: Option<Box<DiagnosticSpanMacroExpansion>>,
+}
+
+/// Information about the macro expansion a span came from.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DiagnosticSpanMacroExpansion {
+    /// The span of the macro invocation.
+    pub span: DiagnosticSpan,
+
+    /// The name of the macro (e.g., `println!`).
+    pub macro_decl_name: String,
+
+    /// The span of the macro definition, if it is known.
+    pub def_site_span: Option<DiagnosticSpan>,
+}
+
+/// A diagnostic message emitted by rustc (or clippy).
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Diagnostic {
+    /// The primary message.
+    pub message: String,
+
+    /// The error code or lint name, if any.
+    pub code: Option<DiagnosticCode>,
+
+    /// The severity of the diagnostic.
+    pub level: DiagnosticLevel,
+
+    /// The source code locations this diagnostic refers to.
+    #[serde(default)]
+    pub spans: Vec<DiagnosticSpan>,
+
+    /// Attached notes, help messages and suggestions.
+    #[serde(default)]
+    pub children: Vec<Diagnostic>,
+
+    /// The diagnostic as rendered by rustc for display on a terminal.
+    pub rendered: Option<String>,
+}
+
+impl Diagnostic {
+    /// Returns the primary span of the diagnostic that lies within the
+    /// workspace, if there is one.
+    ///
+    /// If the primary span is inside a macro defined outside of the
+    /// workspace, the span of the outermost macro invocation that lies within
+    /// the workspace is used instead.
+    fn primary_span(
+        &self,
+        workspace_root: &Path,
+    ) -> Option<(String, &DiagnosticSpan)> {
+        let mut span = self.spans.iter().find(|s| s.is_primary)?;
+
+        loop {
+            if let Some(path) = relativize(&span.file_name, workspace_root) {
+                return Some((path, span));
+            }
+
+            span = &span.expansion.as_ref()?.span;
+        }
+    }
+
+    /// Converts the diagnostic into an annotation.
+    ///
+    /// Paths are made relative to `workspace_root`. Returns `None` if the
+    /// diagnostic has no primary span within the workspace (e.g., "aborting
+    /// due to previous error").
+    pub fn to_annotation(&self, workspace_root: &Path) -> Option<Annotation> {
+        let (path, span) = self.primary_span(workspace_root)?;
+
+        // Columns are only supported by the API when the annotation is on a
+        // single line.
+        let (start_column, end_column) = if span.line_start == span.line_end {
+            (Some(span.column_start), Some(span.column_end))
+        } else {
+            (None, None)
+        };
+
+        Some(Annotation {
+            path,
+            start_line: span.line_start,
+            end_line: span.line_end,
+            start_column,
+            end_column,
+            annotation_level: self.level.into(),
+            message: truncate_annotation_text(self.message.clone()),
+            title: self.code.as_ref().map(|c| c.code.clone()),
+            raw_details: self.rendered.clone().map(truncate_annotation_text),
+        })
+    }
+}
+
+/// Returns the path of `file_name` relative to the workspace root, using `/`
+/// as the separator. Returns `None` if the file is outside of the workspace.
+fn relativize(file_name: &str, workspace_root: &Path) -> Option<String> {
+    let path = Path::new(file_name);
+
+    let path = if path.is_absolute() {
+        path.strip_prefix(workspace_root).ok()?
+    } else {
+        path
+    };
+
+    let mut components = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => components.push(c.to_str()?),
+            Component::CurDir => {}
+            // Anything else (e.g., `../` or a different drive) escapes the
+            // workspace.
+            _ => return None,
+        }
+    }
+
+    if components.is_empty() {
+        return None;
+    }
+
+    Some(components.join("/"))
+}
+
+/// Reads the JSON messages produced by cargo and converts each compiler
+/// message into an annotation.
+///
+/// Lines that are not valid JSON messages are ignored, as are diagnostics
+/// without a location in the workspace. Since cargo may report the same
+/// diagnostic more than once (e.g., once for a library and once for its
+/// tests), duplicate annotations are removed.
+pub fn annotations<R>(
+    reader: R,
+    workspace_root: &Path,
+) -> io::Result<Vec<Annotation>>
+where
+    R: BufRead,
+{
+    let mut seen = HashSet::new();
+    let mut annotations = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        let diagnostic = match serde_json::from_str(&line) {
+            Ok(Message::CompilerMessage { message, .. }) => message,
+            _ => continue,
+        };
+
+        if let Some(annotation) = diagnostic.to_annotation(workspace_root) {
+            if seen.insert(annotation.clone()) {
+                annotations.push(annotation);
+            }
+        }
+    }
+
+    Ok(annotations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    use crate::MAX_ANNOTATION_MESSAGE_SIZE;
+
+    /// A warning, as captured from `cargo build --message-format=json`.
+    fn warning() -> serde_json::Value {
+        let span = json!({
+            "byte_end": 27,
+            "byte_start": 21,
+            "column_end": 15,
+            "column_start": 9,
+            "expansion": null,
+            "file_name": "src/lib.rs",
+            "is_primary": true,
+            "label": null,
+            "line_end": 2,
+            "line_start": 2,
+            "suggested_replacement": null,
+            "suggestion_applicability": null,
+            "text": [{
+                "highlight_end": 15,
+                "highlight_start": 9,
+                "text": "    let unused = 1;"
+            }]
+        });
+
+        let mut suggestion = span.clone();
+        suggestion["suggested_replacement"] = json!("_unused");
+        suggestion["suggestion_applicability"] = json!("MachineApplicable");
+
+        json!({
+            "reason": "compiler-message",
+            "package_id": "path+file:///tmp/capt#0.1.0",
+            "manifest_path": "/tmp/capt/Cargo.toml",
+            "target": {
+                "kind": ["lib"],
+                "crate_types": ["lib"],
+                "name": "capt",
+                "src_path": "/tmp/capt/src/lib.rs",
+                "edition": "2021",
+                "doc": true,
+                "doctest": true,
+                "test": true
+            },
+            "message": {
+                "rendered": concat!(
+                    "warning: unused variable: `unused`\n",
+                    " --> src/lib.rs:2:9\n",
+                    "  |\n",
+                    "2 |     let unused = 1;\n",
+                    "  |         ^^^^^^ help: if this is intentional, prefix ",
+                    "it with an underscore: `_unused`\n",
+                    "  |\n",
+                    "  = note: `#[warn(unused_variables)]` (part of ",
+                    "`#[warn(unused)]`) on by default\n\n",
+                ),
+                "$message_type": "diagnostic",
+                "children": [
+                    {
+                        "children": [],
+                        "code": null,
+                        "level": "note",
+                        "message": concat!(
+                            "`#[warn(unused_variables)]` (part of ",
+                            "`#[warn(unused)]`) on by default",
+                        ),
+                        "rendered": null,
+                        "spans": []
+                    },
+                    {
+                        "children": [],
+                        "code": null,
+                        "level": "help",
+                        "message": concat!(
+                            "if this is intentional, prefix it with an ",
+                            "underscore",
+                        ),
+                        "rendered": null,
+                        "spans": [suggestion]
+                    }
+                ],
+                "level": "warning",
+                "message": "unused variable: `unused`",
+                "spans": [span],
+                "code": {"code": "unused_variables", "explanation": null}
+            }
+        })
+    }
+
+    fn diagnostic(message: serde_json::Value) -> Diagnostic {
+        match serde_json::from_value(message).unwrap() {
+            Message::CompilerMessage { message, .. } => message,
+            message => panic!("unexpected message: {:?}", message),
+        }
+    }
+
+    #[test]
+    fn warning_annotation() {
+        let diagnostic = diagnostic(warning());
+        assert_eq!(diagnostic.children.len(), 2);
+
+        let annotation =
+            diagnostic.to_annotation(Path::new("/tmp/capt")).unwrap();
+
+        assert_eq!(annotation.path, "src/lib.rs");
+        assert_eq!((annotation.start_line, annotation.end_line), (2, 2));
+        assert_eq!(
+            (annotation.start_column, annotation.end_column),
+            (Some(9), Some(15))
+        );
+        assert_eq!(annotation.annotation_level, AnnotationLevel::Warning);
+        assert_eq!(annotation.message, "unused variable: `unused`");
+        assert_eq!(annotation.title.as_deref(), Some("unused_variables"));
+        assert!(annotation
+            .raw_details
+            .unwrap()
+            .starts_with("warning: unused variable"));
+    }
+
+    #[test]
+    fn multiline_spans_have_no_columns() {
+        let mut message = warning();
+        message["message"]["spans"][0]["line_end"] = json!(4);
+        message["message"]["level"] = json!("error");
+
+        let annotation = diagnostic(message)
+            .to_annotation(Path::new("/tmp/capt"))
+            .unwrap();
+
+        assert_eq!((annotation.start_line, annotation.end_line), (2, 4));
+        assert_eq!(
+            (annotation.start_column, annotation.end_column),
+            (None, None)
+        );
+        assert_eq!(annotation.annotation_level, AnnotationLevel::Failure);
+    }
+
+    #[test]
+    fn unknown_level() {
+        let mut message = warning();
+        message["message"]["level"] = json!("future-level");
+        message["message"]["children"][0]["level"] = json!("future-level");
+
+        let diagnostic = diagnostic(message);
+        assert_eq!(diagnostic.level, DiagnosticLevel::Other);
+
+        let annotation =
+            diagnostic.to_annotation(Path::new("/tmp/capt")).unwrap();
+        assert_eq!(annotation.annotation_level, AnnotationLevel::Failure);
+    }
+
+    #[test]
+    fn large_diagnostics_are_truncated() {
+        // Multibyte characters make sure the cut is on a UTF-8 boundary.
+        let mut message = warning();
+        message["message"]["message"] =
+            json!(format!("a{}", "é".repeat(40_000)));
+        message["message"]["rendered"] = json!("x".repeat(100_000));
+
+        let annotation = diagnostic(message)
+            .to_annotation(Path::new("/tmp/capt"))
+            .unwrap();
+
+        assert_eq!(annotation.message.len(), MAX_ANNOTATION_MESSAGE_SIZE - 1);
+        assert!(annotation.message.ends_with('é'));
+        assert_eq!(
+            annotation.raw_details.unwrap().len(),
+            MAX_ANNOTATION_MESSAGE_SIZE
+        );
+    }
+
+    #[test]
+    fn external_macro_expansion() {
+        // A span inside a macro defined in a dependency is attributed to the
+        // macro invocation in the workspace.
+        let mut message = warning();
+        let invocation = message["message"]["spans"][0].clone();
+        message["message"]["spans"][0]["file_name"] =
+            json!("/root/.cargo/registry/src/dep-1.0.0/src/lib.rs");
+        message["message"]["spans"][0]["line_start"] = json!(100);
+        message["message"]["spans"][0]["expansion"] = json!({
+            "span": invocation,
+            "macro_decl_name": "dep::m!",
+            "def_site_span": null
+        });
+
+        let annotation = diagnostic(message)
+            .to_annotation(Path::new("/tmp/capt"))
+            .unwrap();
+
+        assert_eq!(annotation.path, "src/lib.rs");
+        assert_eq!(annotation.start_line, 2);
+    }
+
+    #[test]
+    fn outside_workspace() {
+        let mut message = warning();
+        message["message"]["spans"][0]["file_name"] =
+            json!("/elsewhere/src/lib.rs");
+
+        assert_eq!(
+            diagnostic(message).to_annotation(Path::new("/tmp/capt")),
+            None
+        );
+    }
+
+    #[test]
+    fn relative_paths() {
+        let root = Path::new("/work");
+
+        assert_eq!(relativize("src/lib.rs", root).unwrap(), "src/lib.rs");
+        assert_eq!(relativize("./src/lib.rs", root).unwrap(), "src/lib.rs");
+        assert_eq!(relativize("/work/a/b.rs", root).unwrap(), "a/b.rs");
+        assert_eq!(relativize("/workspace/b.rs", root), None);
+        assert_eq!(relativize("/other/b.rs", root), None);
+        assert_eq!(relativize("../b.rs", root), None);
+        assert_eq!(relativize("a/../../b.rs", root), None);
+        assert_eq!(relativize("/work", root), None);
+    }
+
+    #[test]
+    fn reads_messages() {
+        let warning = warning().to_string();
+        let error = json!({
+            "reason": "compiler-message",
+            "package_id": "path+file:///tmp/capt#0.1.0",
+            "message": {
+                "message": "aborting due to 1 previous error",
+                "code": null,
+                "level": "error",
+                "spans": [],
+                "children": [],
+                "rendered": "error: aborting due to 1 previous error\n\n"
+            }
+        });
+        let artifact = json!({
+            "reason": "compiler-artifact",
+            "package_id": "path+file:///tmp/capt#0.1.0",
+            "fresh": false
+        });
+        let finished = json!({"reason": "build-finished", "success": false});
+
+        assert_eq!(
+            serde_json::from_value::<Message>(artifact.clone()).unwrap(),
+            Message::Other
+        );
+        assert_eq!(
+            serde_json::from_value::<Message>(finished.clone()).unwrap(),
+            Message::BuildFinished { success: false }
+        );
+
+        // The same warning is reported for the library and its tests.
+        let output = [
+            artifact.to_string(),
+            warning.clone(),
+            "   Compiling capt v0.1.0".to_string(),
+            error.to_string(),
+            warning,
+            finished.to_string(),
+        ]
+        .join("\n");
+
+        let annotations =
+            annotations(output.as_bytes(), Path::new("/tmp/capt")).unwrap();
+
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].title.as_deref(), Some("unused_variables"));
+    }
+}
@@ -0,0 +1,1008 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Request bodies for creating and updating check runs.
+//!
+//! See: https://developer.github.com/v3/checks/runs/
+
+use std::error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CheckRunAction, CheckRunStatus, Conclusion, DateTime, Oid, Output,
+};
+
+/// The maximum number of actions a check run may have.
+pub const MAX_ACTIONS: usize = 3;
+
+/// The maximum number of characters in a `CheckRunAction::label`.
+pub const MAX_ACTION_LABEL_LEN: usize = 20;
+
+/// The maximum number of characters in a `CheckRunAction::description`.
+pub const MAX_ACTION_DESCRIPTION_LEN: usize = 40;
+
+/// The maximum number of characters in a `CheckRunAction::identifier`.
+pub const MAX_ACTION_IDENTIFIER_LEN: usize = 20;
+
+/// The maximum number of annotations that may be sent in a single request.
+pub const MAX_ANNOTATIONS: usize = 50;
+
+/// The maximum size, in bytes, of `Annotation::message` and
+/// `Annotation::raw_details`.
+pub const MAX_ANNOTATION_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// The maximum number of characters in an `Annotation::title`.
+pub const MAX_ANNOTATION_TITLE_LEN: usize = 255;
+
+/// The maximum number of characters in `Output::summary` and `Output::text`.
+pub const MAX_OUTPUT_TEXT_LEN: usize = 65535;
+
+/// A violation of one of GitHub's documented check run limits.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CheckRunError {
+    /// More than `MAX_ACTIONS` actions were given.
+    TooManyActions(usize),
+
+    /// An action label is longer than `MAX_ACTION_LABEL_LEN` characters.
+    ActionLabelTooLong(String),
+
+    /// An action description is longer than `MAX_ACTION_DESCRIPTION_LEN`
+    /// characters.
+    ActionDescriptionTooLong(String),
+
+    /// An action identifier is longer than `MAX_ACTION_IDENTIFIER_LEN`
+    /// characters.
+    ActionIdentifierTooLong(String),
+
+    /// More than `MAX_ANNOTATIONS` annotations were given.
+    TooManyAnnotations(usize),
+
+    /// The message of the annotation for the given path is larger than
+    /// `MAX_ANNOTATION_MESSAGE_SIZE` bytes.
+    AnnotationMessageTooLarge(String),
+
+    /// The raw details of the annotation for the given path are larger than
+    /// `MAX_ANNOTATION_MESSAGE_SIZE` bytes.
+    AnnotationDetailsTooLarge(String),
+
+    /// An annotation title is longer than `MAX_ANNOTATION_TITLE_LEN`
+    /// characters.
+    AnnotationTitleTooLong(String),
+
+    /// The output summary is longer than `MAX_OUTPUT_TEXT_LEN` characters.
+    SummaryTooLong(usize),
+
+    /// The output text is longer than `MAX_OUTPUT_TEXT_LEN` characters.
+    TextTooLong(usize),
+
+    /// The status is `Completed` or `completed_at` was given, but there is no
+    /// conclusion.
+    MissingConclusion,
+}
+
+impl fmt::Display for CheckRunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckRunError::TooManyActions(n) => write!(
+                f,
+                "check run has {} actions, but at most {} are allowed",
+                n, MAX_ACTIONS
+            ),
+            CheckRunError::ActionLabelTooLong(label) => write!(
+                f,
+                "action label {:?} is longer than {} characters",
+                label, MAX_ACTION_LABEL_LEN
+            ),
+            CheckRunError::ActionDescriptionTooLong(description) => write!(
+                f,
+                "action description {:?} is longer than {} characters",
+                description, MAX_ACTION_DESCRIPTION_LEN
+            ),
+            CheckRunError::ActionIdentifierTooLong(identifier) => write!(
+                f,
+                "action identifier {:?} is longer than {} characters",
+                identifier, MAX_ACTION_IDENTIFIER_LEN
+            ),
+            CheckRunError::TooManyAnnotations(n) => write!(
+                f,
+                "output has {} annotations, but at most {} are allowed per \
+                 request",
+                n, MAX_ANNOTATIONS
+            ),
+            CheckRunError::AnnotationMessageTooLarge(path) => write!(
+                f,
+                "message of annotation for {:?} is larger than {} bytes",
+                path, MAX_ANNOTATION_MESSAGE_SIZE
+            ),
+            CheckRunError::AnnotationDetailsTooLarge(path) => write!(
+                f,
+                "raw details of annotation for {:?} are larger than {} bytes",
+                path, MAX_ANNOTATION_MESSAGE_SIZE
+            ),
+            CheckRunError::AnnotationTitleTooLong(title) => write!(
+                f,
+                "annotation title {:?} is longer than {} characters",
+                title, MAX_ANNOTATION_TITLE_LEN
+            ),
+            CheckRunError::SummaryTooLong(n) => write!(
+                f,
+                "output summary has {} characters, but at most {} are allowed",
+                n, MAX_OUTPUT_TEXT_LEN
+            ),
+            CheckRunError::TextTooLong(n) => write!(
+                f,
+                "output text has {} characters, but at most {} are allowed",
+                n, MAX_OUTPUT_TEXT_LEN
+            ),
+            CheckRunError::MissingConclusion => f.write_str(
+                "a conclusion is required when the check run is completed",
+            ),
+        }
+    }
+}
+
+impl error::Error for CheckRunError {}
+
+fn validate_actions(actions: &[CheckRunAction]) -> Result<(), CheckRunError> {
+    if actions.len() > MAX_ACTIONS {
+        return Err(CheckRunError::TooManyActions(actions.len()));
+    }
+
+    for action in actions {
+        if action.label.chars().count() > MAX_ACTION_LABEL_LEN {
+            return Err(CheckRunError::ActionLabelTooLong(
+                action.label.clone(),
+            ));
+        }
+
+        if action.description.chars().count() > MAX_ACTION_DESCRIPTION_LEN {
+            return Err(CheckRunError::ActionDescriptionTooLong(
+                action.description.clone(),
+            ));
+        }
+
+        if action.identifier.chars().count() > MAX_ACTION_IDENTIFIER_LEN {
+            return Err(CheckRunError::ActionIdentifierTooLong(
+                action.identifier.clone(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_output(output: &Output) -> Result<(), CheckRunError> {
+    let summary_len = output.summary.chars().count();
+    if summary_len > MAX_OUTPUT_TEXT_LEN {
+        return Err(CheckRunError::SummaryTooLong(summary_len));
+    }
+
+    if let Some(text) = &output.text {
+        let text_len = text.chars().count();
+        if text_len > MAX_OUTPUT_TEXT_LEN {
+            return Err(CheckRunError::TextTooLong(text_len));
+        }
+    }
+
+    let annotations = output.annotations.as_deref().unwrap_or(&[]);
+
+    if annotations.len() > MAX_ANNOTATIONS {
+        return Err(CheckRunError::TooManyAnnotations(annotations.len()));
+    }
+
+    for annotation in annotations {
+        if annotation.message.len() > MAX_ANNOTATION_MESSAGE_SIZE {
+            return Err(CheckRunError::AnnotationMessageTooLarge(
+                annotation.path.clone(),
+            ));
+        }
+
+        if let Some(raw_details) = &annotation.raw_details {
+            if raw_details.len() > MAX_ANNOTATION_MESSAGE_SIZE {
+                return Err(CheckRunError::AnnotationDetailsTooLarge(
+                    annotation.path.clone(),
+                ));
+            }
+        }
+
+        if let Some(title) = &annotation.title {
+            if title.chars().count() > MAX_ANNOTATION_TITLE_LEN {
+                return Err(CheckRunError::AnnotationTitleTooLong(
+                    title.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate(
+    status: Option<CheckRunStatus>,
+    conclusion: Option<Conclusion>,
+    completed_at: Option<&DateTime>,
+    output: Option<&Output>,
+    actions: Option<&[CheckRunAction]>,
+) -> Result<(), CheckRunError> {
+    let completed =
+        status == Some(CheckRunStatus::Completed) || completed_at.is_some();
+
+    if completed && conclusion.is_none() {
+        return Err(CheckRunError::MissingConclusion);
+    }
+
+    if let Some(output) = output {
+        validate_output(output)?;
+    }
+
+    if let Some(actions) = actions {
+        validate_actions(actions)?;
+    }
+
+    Ok(())
+}
+
+/// Cuts `text` to at most `MAX_ANNOTATION_MESSAGE_SIZE` bytes, on a UTF-8
+/// boundary, so that it can be used as an annotation's message or raw
+/// details.
+pub(crate) fn truncate_annotation_text(mut text: String) -> String {
+    if text.len() > MAX_ANNOTATION_MESSAGE_SIZE {
+        let mut end = MAX_ANNOTATION_MESSAGE_SIZE;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+
+    text
+}
+
+/// The request body for creating a check run.
+///
+/// See: https://developer.github.com/v3/checks/runs/#create-a-check-run
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NewCheckRun {
+    /// Required. The name of the check (e.g., "code-coverage").
+    pub name: String,
+
+    /// Required. The SHA of the commit.
+    pub head_sha: Oid,
+
+    /// The URL of the integrator's site that has the full details of the
+    /// check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details_url: Option<String>,
+
+    /// A reference for the run on the integrator's system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+
+    /// The current status. GitHub defaults to `Queued`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<CheckRunStatus>,
+
+    /// The time that the check run began.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime>,
+
+    /// The final conclusion of the check. Required if `completed_at` is
+    /// given or the status is `Completed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion: Option<Conclusion>,
+
+    /// The time the check completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime>,
+
+    /// Check runs can accept a variety of data in the output object,
+    /// including a title and summary and can optionally provide descriptive
+    /// details about the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Output>,
+
+    /// Possible further actions the integrator can perform, which a user may
+    /// trigger. A maximum of three actions are accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<CheckRunAction>>,
+}
+
+impl NewCheckRun {
+    /// Returns a builder for a check run with the given name and commit.
+    pub fn builder<S>(name: S, head_sha: Oid) -> NewCheckRunBuilder
+    where
+        S: Into<String>,
+    {
+        NewCheckRunBuilder {
+            inner: NewCheckRun {
+                name: name.into(),
+                head_sha,
+                details_url: None,
+                external_id: None,
+                status: None,
+                started_at: None,
+                conclusion: None,
+                completed_at: None,
+                output: None,
+                actions: None,
+            },
+        }
+    }
+
+    /// Checks that this request is within GitHub's documented limits.
+    pub fn validate(&self) -> Result<(), CheckRunError> {
+        validate(
+            self.status,
+            self.conclusion,
+            self.completed_at.as_ref(),
+            self.output.as_ref(),
+            self.actions.as_deref(),
+        )
+    }
+
+    /// Splits the output into batches of at most `MAX_ANNOTATIONS`
+    /// annotations. See `Output::into_batches`.
+    ///
+    /// Returns the request to create the check run with the first batch,
+    /// followed by the updates that append the remaining annotations. If the
+    /// check run is completed, the status, conclusion, and `completed_at` are
+    /// sent with the last update so that the check run doesn't appear to be
+    /// done before all of its annotations have been added. Until then, it is
+    /// `InProgress`.
+    pub fn into_batches(mut self) -> (NewCheckRun, Vec<UpdateCheckRun>) {
+        let mut batches = self
+            .output
+            .take()
+            .map(Output::into_batches)
+            .unwrap_or_default()
+            .into_iter();
+
+        self.output = batches.next();
+
+        let mut updates: Vec<_> = batches
+            .map(|output| UpdateCheckRun {
+                output: Some(output),
+                ..UpdateCheckRun::default()
+            })
+            .collect();
+
+        let completed = self.status == Some(CheckRunStatus::Completed)
+            || self.conclusion.is_some()
+            || self.completed_at.is_some();
+
+        if let Some(last) = updates.last_mut() {
+            if completed {
+                last.status = self.status.take();
+                last.conclusion = self.conclusion.take();
+                last.completed_at = self.completed_at.take();
+                self.status = Some(CheckRunStatus::InProgress);
+            }
+        }
+
+        (self, updates)
+    }
+}
+
+/// A builder for `NewCheckRun`.
+#[derive(Debug, Clone)]
+pub struct NewCheckRunBuilder {
+    inner: NewCheckRun,
+}
+
+impl NewCheckRunBuilder {
+    pub fn details_url<S: Into<String>>(mut self, details_url: S) -> Self {
+        self.inner.details_url = Some(details_url.into());
+        self
+    }
+
+    pub fn external_id<S: Into<String>>(mut self, external_id: S) -> Self {
+        self.inner.external_id = Some(external_id.into());
+        self
+    }
+
+    pub fn status(mut self, status: CheckRunStatus) -> Self {
+        self.inner.status = Some(status);
+        self
+    }
+
+    pub fn started_at(mut self, started_at: DateTime) -> Self {
+        self.inner.started_at = Some(started_at);
+        self
+    }
+
+    /// Sets the conclusion. GitHub implicitly sets the status to `Completed`
+    /// when a conclusion is given.
+    pub fn conclusion(mut self, conclusion: Conclusion) -> Self {
+        self.inner.conclusion = Some(conclusion);
+        self
+    }
+
+    pub fn completed_at(mut self, completed_at: DateTime) -> Self {
+        self.inner.completed_at = Some(completed_at);
+        self
+    }
+
+    pub fn output(mut self, output: Output) -> Self {
+        self.inner.output = Some(output);
+        self
+    }
+
+    /// Adds an action.
+    pub fn action(mut self, action: CheckRunAction) -> Self {
+        self.inner.actions.get_or_insert_with(Vec::new).push(action);
+        self
+    }
+
+    /// Validates and returns the request.
+    pub fn build(self) -> Result<NewCheckRun, CheckRunError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+}
+
+/// The request body for updating a check run. Only the fields that are set
+/// are changed.
+///
+/// See: https://developer.github.com/v3/checks/runs/#update-a-check-run
+#[derive(
+    Serialize,
+    Deserialize,
+    Default,
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateCheckRun {
+    /// The name of the check (e.g., "code-coverage").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The URL of the integrator's site that has the full details of the
+    /// check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details_url: Option<String>,
+
+    /// A reference for the run on the integrator's system.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+
+    /// The current status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<CheckRunStatus>,
+
+    /// The time that the check run began.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime>,
+
+    /// The final conclusion of the check. Required if `completed_at` is
+    /// given or the status is `Completed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conclusion: Option<Conclusion>,
+
+    /// The time the check completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime>,
+
+    /// Check runs can accept a variety of data in the output object. Any
+    /// annotations are appended to those that already exist.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Output>,
+
+    /// Possible further actions the integrator can perform, which a user may
+    /// trigger. A maximum of three actions are accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<CheckRunAction>>,
+}
+
+impl UpdateCheckRun {
+    /// Returns a builder for a check run update.
+    pub fn builder() -> UpdateCheckRunBuilder {
+        UpdateCheckRunBuilder {
+            inner: UpdateCheckRun::default(),
+        }
+    }
+
+    /// Checks that this request is within GitHub's documented limits.
+    pub fn validate(&self) -> Result<(), CheckRunError> {
+        validate(
+            self.status,
+            self.conclusion,
+            self.completed_at.as_ref(),
+            self.output.as_ref(),
+            self.actions.as_deref(),
+        )
+    }
+
+    /// Splits the output into batches of at most `MAX_ANNOTATIONS`
+    /// annotations. See `Output::into_batches`.
+    ///
+    /// The first update contains the other fields of this update, while the
+    /// following updates only append the remaining annotations. The status,
+    /// conclusion, and `completed_at` are sent with the last update so that
+    /// the check run doesn't appear to be done before all of its annotations
+    /// have been added.
+    pub fn into_batches(mut self) -> Vec<UpdateCheckRun> {
+        let batches = match self.output.take() {
+            Some(output) => output.into_batches(),
+            None => return vec![self],
+        };
+
+        let mut batches = batches.into_iter();
+        self.output = batches.next();
+
+        let status = self.status.take();
+        let conclusion = self.conclusion.take();
+        let completed_at = self.completed_at.take();
+
+        let mut updates = vec![self];
+
+        updates.extend(batches.map(|output| UpdateCheckRun {
+            output: Some(output),
+            ..UpdateCheckRun::default()
+        }));
+
+        let last = updates.last_mut().unwrap();
+        last.status = status;
+        last.conclusion = conclusion;
+        last.completed_at = completed_at;
+
+        updates
+    }
+}
+
+/// A builder for `UpdateCheckRun`.
+#[derive(Debug, Clone)]
+pub struct UpdateCheckRunBuilder {
+    inner: UpdateCheckRun,
+}
+
+impl UpdateCheckRunBuilder {
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.inner.name = Some(name.into());
+        self
+    }
+
+    pub fn details_url<S: Into<String>>(mut self, details_url: S) -> Self {
+        self.inner.details_url = Some(details_url.into());
+        self
+    }
+
+    pub fn external_id<S: Into<String>>(mut self, external_id: S) -> Self {
+        self.inner.external_id = Some(external_id.into());
+        self
+    }
+
+    pub fn status(mut self, status: CheckRunStatus) -> Self {
+        self.inner.status = Some(status);
+        self
+    }
+
+    pub fn started_at(mut self, started_at: DateTime) -> Self {
+        self.inner.started_at = Some(started_at);
+        self
+    }
+
+    /// Sets the conclusion. GitHub implicitly sets the status to `Completed`
+    /// when a conclusion is given.
+    pub fn conclusion(mut self, conclusion: Conclusion) -> Self {
+        self.inner.conclusion = Some(conclusion);
+        self
+    }
+
+    pub fn completed_at(mut self, completed_at: DateTime) -> Self {
+        self.inner.completed_at = Some(completed_at);
+        self
+    }
+
+    pub fn output(mut self, output: Output) -> Self {
+        self.inner.output = Some(output);
+        self
+    }
+
+    /// Adds an action.
+    pub fn action(mut self, action: CheckRunAction) -> Self {
+        self.inner.actions.get_or_insert_with(Vec::new).push(action);
+        self
+    }
+
+    /// Validates and returns the request.
+    pub fn build(self) -> Result<UpdateCheckRun, CheckRunError> {
+        self.inner.validate()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Annotation, AnnotationLevel};
+
+    fn output() -> Output {
+        Output {
+            title: "Lint".to_string(),
+            summary: "Done.".to_string(),
+            text: None,
+            annotations: None,
+            images: None,
+        }
+    }
+
+    fn annotation(level: AnnotationLevel) -> Annotation {
+        Annotation {
+            path: "src/lib.rs".to_string(),
+            start_line: 1,
+            end_line: 1,
+            start_column: None,
+            end_column: None,
+            annotation_level: level,
+            message: "message".to_string(),
+            title: None,
+            raw_details: None,
+        }
+    }
+
+    fn action() -> CheckRunAction {
+        CheckRunAction {
+            label: "Fix".to_string(),
+            description: "Fix it.".to_string(),
+            identifier: "fix".to_string(),
+        }
+    }
+
+    /// Validates the output with both builders, which must agree.
+    fn validate_output(output: Output) -> Result<(), CheckRunError> {
+        let new = NewCheckRun::builder("lint", Oid::ZERO)
+            .output(output.clone())
+            .build()
+            .map(drop);
+        let update = UpdateCheckRun::builder().output(output).build().map(drop);
+        assert_eq!(new, update);
+        new
+    }
+
+    /// Validates the action with both builders, which must agree.
+    fn validate_action(action: CheckRunAction) -> Result<(), CheckRunError> {
+        let new = NewCheckRun::builder("lint", Oid::ZERO)
+            .action(action.clone())
+            .build()
+            .map(drop);
+        let update = UpdateCheckRun::builder().action(action).build().map(drop);
+        assert_eq!(new, update);
+        new
+    }
+
+    #[test]
+    fn summary_too_long() {
+        let mut output = output();
+        output.summary = "é".repeat(MAX_OUTPUT_TEXT_LEN);
+        assert_eq!(validate_output(output.clone()), Ok(()));
+
+        output.summary.push('a');
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::SummaryTooLong(MAX_OUTPUT_TEXT_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn text_too_long() {
+        let mut output = output();
+        output.text = Some("é".repeat(MAX_OUTPUT_TEXT_LEN));
+        assert_eq!(validate_output(output.clone()), Ok(()));
+
+        output.text = Some("a".repeat(MAX_OUTPUT_TEXT_LEN + 1));
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::TextTooLong(MAX_OUTPUT_TEXT_LEN + 1))
+        );
+    }
+
+    #[test]
+    fn too_many_annotations() {
+        let mut output = output();
+        output.annotations =
+            Some(vec![annotation(AnnotationLevel::Notice); MAX_ANNOTATIONS]);
+        assert_eq!(validate_output(output.clone()), Ok(()));
+
+        output
+            .annotations
+            .as_mut()
+            .unwrap()
+            .push(annotation(AnnotationLevel::Notice));
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::TooManyAnnotations(MAX_ANNOTATIONS + 1))
+        );
+    }
+
+    #[test]
+    fn annotation_message_too_large() {
+        let mut annotation = annotation(AnnotationLevel::Warning);
+        annotation.message = "a".repeat(MAX_ANNOTATION_MESSAGE_SIZE + 1);
+
+        let mut output = output();
+        output.annotations = Some(vec![annotation]);
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::AnnotationMessageTooLarge(
+                "src/lib.rs".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn annotation_details_too_large() {
+        // The limit is in bytes, not characters.
+        let mut annotation = annotation(AnnotationLevel::Warning);
+        annotation.raw_details =
+            Some("é".repeat(MAX_ANNOTATION_MESSAGE_SIZE / 2 + 1));
+
+        let mut output = output();
+        output.annotations = Some(vec![annotation]);
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::AnnotationDetailsTooLarge(
+                "src/lib.rs".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn annotation_title_too_long() {
+        let mut annotation = annotation(AnnotationLevel::Failure);
+        annotation.title = Some("é".repeat(MAX_ANNOTATION_TITLE_LEN));
+
+        let mut output = output();
+        output.annotations = Some(vec![annotation.clone()]);
+        assert_eq!(validate_output(output.clone()), Ok(()));
+
+        let title = "a".repeat(MAX_ANNOTATION_TITLE_LEN + 1);
+        annotation.title = Some(title.clone());
+        output.annotations = Some(vec![annotation]);
+        assert_eq!(
+            validate_output(output),
+            Err(CheckRunError::AnnotationTitleTooLong(title))
+        );
+    }
+
+    #[test]
+    fn too_many_actions() {
+        let mut builder = NewCheckRun::builder("lint", Oid::ZERO);
+
+        for _ in 0..MAX_ACTIONS {
+            builder = builder.action(action());
+        }
+
+        assert!(builder.clone().build().is_ok());
+        assert_eq!(
+            builder.action(action()).build(),
+            Err(CheckRunError::TooManyActions(MAX_ACTIONS + 1))
+        );
+    }
+
+    #[test]
+    fn action_label_too_long() {
+        let mut action = action();
+        action.label = "a".repeat(MAX_ACTION_LABEL_LEN + 1);
+        assert_eq!(
+            validate_action(action.clone()),
+            Err(CheckRunError::ActionLabelTooLong(action.label))
+        );
+    }
+
+    #[test]
+    fn action_description_too_long() {
+        let mut action = action();
+        action.description = "a".repeat(MAX_ACTION_DESCRIPTION_LEN + 1);
+        assert_eq!(
+            validate_action(action.clone()),
+            Err(CheckRunError::ActionDescriptionTooLong(action.description))
+        );
+    }
+
+    #[test]
+    fn action_identifier_too_long() {
+        let mut action = action();
+        action.identifier = "a".repeat(MAX_ACTION_IDENTIFIER_LEN + 1);
+        assert_eq!(
+            validate_action(action.clone()),
+            Err(CheckRunError::ActionIdentifierTooLong(action.identifier))
+        );
+    }
+
+    /// Returns an output with `n` annotations of each level, in increasing
+    /// order of severity.
+    fn annotated(n: usize) -> Output {
+        let mut annotations = Vec::new();
+
+        for &level in &[
+            AnnotationLevel::Notice,
+            AnnotationLevel::Warning,
+            AnnotationLevel::Failure,
+        ] {
+            annotations.extend(vec![annotation(level); n]);
+        }
+
+        Output {
+            text: Some("Details.".to_string()),
+            annotations: Some(annotations),
+            ..output()
+        }
+    }
+
+    fn levels(output: &Option<Output>) -> Vec<AnnotationLevel> {
+        output
+            .as_ref()
+            .and_then(|o| o.annotations.as_ref())
+            .map_or_else(Vec::new, |a| {
+                a.iter().map(|a| a.annotation_level).collect()
+            })
+    }
+
+    #[test]
+    fn update_batches() {
+        let completed_at = DateTime::from_timestamp(1_557_933_633).unwrap();
+
+        // This is too large for the builder.
+        let updates = UpdateCheckRun {
+            name: Some("lint".to_string()),
+            status: Some(CheckRunStatus::Completed),
+            conclusion: Some(Conclusion::Failure),
+            completed_at: Some(completed_at.clone()),
+            output: Some(annotated(40)),
+            ..UpdateCheckRun::default()
+        }
+        .into_batches();
+
+        let sizes: Vec<_> =
+            updates.iter().map(|u| levels(&u.output).len()).collect();
+        assert_eq!(sizes, [50, 50, 20]);
+
+        // Failures are sent first.
+        assert_eq!(
+            levels(&updates[0].output)[..40],
+            [AnnotationLevel::Failure; 40]
+        );
+        assert_eq!(levels(&updates[2].output), [AnnotationLevel::Notice; 20]);
+
+        // The other fields go with the first batch.
+        assert_eq!(updates[0].name.as_deref(), Some("lint"));
+        assert_eq!(
+            updates[0].output.as_ref().unwrap().text.as_deref(),
+            Some("Details.")
+        );
+        assert_eq!(updates[1].name, None);
+        assert_eq!(updates[1].output.as_ref().unwrap().text, None);
+
+        // The completion goes with the last batch.
+        for update in &updates[..2] {
+            assert_eq!(update.status, None);
+            assert_eq!(update.conclusion, None);
+            assert_eq!(update.completed_at, None);
+        }
+
+        assert_eq!(updates[2].status, Some(CheckRunStatus::Completed));
+        assert_eq!(updates[2].conclusion, Some(Conclusion::Failure));
+        assert_eq!(updates[2].completed_at, Some(completed_at));
+
+        for update in &updates {
+            assert_eq!(update.validate(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn update_single_batch() {
+        let updates = UpdateCheckRun::builder()
+            .conclusion(Conclusion::Success)
+            .output(annotated(1))
+            .build()
+            .unwrap()
+            .into_batches();
+
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].conclusion, Some(Conclusion::Success));
+        assert_eq!(
+            levels(&updates[0].output),
+            [
+                AnnotationLevel::Failure,
+                AnnotationLevel::Warning,
+                AnnotationLevel::Notice
+            ]
+        );
+    }
+
+    #[test]
+    fn new_batches() {
+        let new = NewCheckRun {
+            conclusion: Some(Conclusion::Failure),
+            output: Some(annotated(20)),
+            ..NewCheckRun::builder("lint", Oid::ZERO).build().unwrap()
+        };
+
+        let (new, updates) = new.into_batches();
+
+        assert_eq!(levels(&new.output).len(), 50);
+        assert_eq!(levels(&new.output)[..20], [AnnotationLevel::Failure; 20]);
+        assert_eq!(new.status, Some(CheckRunStatus::InProgress));
+        assert_eq!(new.conclusion, None);
+        assert_eq!(new.validate(), Ok(()));
+
+        assert_eq!(updates.len(), 1);
+        assert_eq!(levels(&updates[0].output), [AnnotationLevel::Notice; 10]);
+        assert_eq!(updates[0].conclusion, Some(Conclusion::Failure));
+        assert_eq!(updates[0].validate(), Ok(()));
+    }
+
+    #[test]
+    fn new_single_batch() {
+        let (new, updates) = NewCheckRun::builder("lint", Oid::ZERO)
+            .conclusion(Conclusion::Success)
+            .output(annotated(1))
+            .build()
+            .unwrap()
+            .into_batches();
+
+        assert!(updates.is_empty());
+        assert_eq!(new.status, None);
+        assert_eq!(new.conclusion, Some(Conclusion::Success));
+        assert_eq!(levels(&new.output).len(), 3);
+    }
+
+    #[test]
+    fn missing_conclusion() {
+        let completed_at = DateTime::from_timestamp(1_557_933_633).unwrap();
+
+        assert_eq!(
+            NewCheckRun::builder("lint", Oid::ZERO)
+                .status(CheckRunStatus::Completed)
+                .build(),
+            Err(CheckRunError::MissingConclusion)
+        );
+        assert_eq!(
+            UpdateCheckRun::builder()
+                .completed_at(completed_at.clone())
+                .build(),
+            Err(CheckRunError::MissingConclusion)
+        );
+
+        assert!(NewCheckRun::builder("lint", Oid::ZERO)
+            .status(CheckRunStatus::Completed)
+            .completed_at(completed_at)
+            .conclusion(Conclusion::Success)
+            .build()
+            .is_ok());
+        assert!(UpdateCheckRun::builder()
+            .status(CheckRunStatus::InProgress)
+            .build()
+            .is_ok());
+    }
+}
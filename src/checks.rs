@@ -18,23 +18,62 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{App, DateTime, Oid};
+use std::cmp::Reverse;
+
+use crate::{
+    App, CheckRunId, CheckSuiteId, DateTime, IssueNumber, Oid, PullRequestId,
+    PushAuthor, RepoId, Repository, MAX_ANNOTATIONS,
+};
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CheckRunStatus {
     Queued,
     InProgress,
     Completed,
+
+    /// The check run is waiting for a deployment protection rule to be
+    /// satisfied. This is only used by GitHub Actions.
+    Waiting,
+
+    /// The check run has been requested but not yet queued. This is only
+    /// used by GitHub Actions.
+    Requested,
+
+    /// The check run is pending behind another run in the same concurrency
+    /// group. This is only used by GitHub Actions.
+    Pending,
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum Conclusion {
     Success,
@@ -43,11 +82,139 @@ pub enum Conclusion {
     Cancelled,
     TimedOut,
     ActionRequired,
+
+    /// The check run was skipped (e.g., because a workflow job's condition
+    /// was not met).
+    Skipped,
+
+    /// The check run was marked stale by GitHub because it was incomplete
+    /// for too long.
+    Stale,
+
+    /// The workflow failed to start (e.g., because the workflow file is
+    /// invalid).
+    StartupFailure,
+}
+
+impl Conclusion {
+    /// Returns the precedence of this conclusion when combining several
+    /// conclusions into one. The conclusion with the highest precedence wins.
+    fn precedence(self) -> u8 {
+        match self {
+            Conclusion::Skipped => 0,
+            Conclusion::Neutral => 1,
+            Conclusion::Success => 2,
+            Conclusion::Stale => 3,
+            Conclusion::Failure => 4,
+            Conclusion::TimedOut => 5,
+            Conclusion::Cancelled => 6,
+            Conclusion::StartupFailure => 7,
+            Conclusion::ActionRequired => 8,
+        }
+    }
+}
+
+impl CheckRunStatus {
+    /// Returns the precedence of this status when combining several
+    /// statuses into one. The status with the highest precedence wins, except
+    /// that `Completed` is only used if every status is `Completed`.
+    fn precedence(self) -> u8 {
+        match self {
+            CheckRunStatus::Completed => 0,
+            CheckRunStatus::Requested => 1,
+            CheckRunStatus::Pending => 2,
+            CheckRunStatus::Waiting => 3,
+            CheckRunStatus::Queued => 4,
+            CheckRunStatus::InProgress => 5,
+        }
+    }
+}
+
+/// The combined status and conclusion of a set of check runs, as GitHub
+/// computes it for a check suite.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CheckSuiteSummary {
+    /// `Completed` if every check run has completed. Otherwise, the most
+    /// active status of the incomplete check runs, in the order
+    /// `InProgress`, `Queued`, `Waiting`, `Pending` and `Requested`.
+    pub status: CheckRunStatus,
+
+    /// The combined conclusion. This is `None` until every check run has
+    /// completed.
+    ///
+    /// The most severe conclusion wins, in the order `ActionRequired`,
+    /// `StartupFailure`, `Cancelled`, `TimedOut`, `Failure`, `Stale`,
+    /// `Success`, `Neutral` and `Skipped`. That is, a suite is only
+    /// `Neutral` or `Skipped` if none of its check runs succeeded or failed.
+    pub conclusion: Option<Conclusion>,
+}
+
+impl CheckSuiteSummary {
+    /// Computes the summary from the statuses and conclusions of a set of
+    /// check runs. An empty set of check runs is `Queued`.
+    pub fn new<I>(runs: I) -> Self
+    where
+        I: IntoIterator<Item = (CheckRunStatus, Option<Conclusion>)>,
+    {
+        let mut status = None;
+        let mut conclusion: Option<Conclusion> = None;
+
+        for (s, c) in runs {
+            if status.is_none_or(|status: CheckRunStatus| {
+                s.precedence() > status.precedence()
+            }) {
+                status = Some(s);
+            }
+
+            if let Some(c) = c {
+                if conclusion.is_none_or(|conclusion| {
+                    c.precedence() > conclusion.precedence()
+                }) {
+                    conclusion = Some(c);
+                }
+            }
+        }
+
+        match status {
+            Some(CheckRunStatus::Completed) => CheckSuiteSummary {
+                status: CheckRunStatus::Completed,
+                conclusion,
+            },
+            Some(status) => CheckSuiteSummary {
+                status,
+                conclusion: None,
+            },
+            None => CheckSuiteSummary {
+                status: CheckRunStatus::Queued,
+                conclusion: None,
+            },
+        }
+    }
+
+    /// Computes the summary of a set of check runs.
+    pub fn from_check_runs<'a, I, A>(runs: I) -> Self
+    where
+        I: IntoIterator<Item = &'a CheckRun<A>>,
+        A: 'a,
+    {
+        Self::new(runs.into_iter().map(|r| (r.status, r.conclusion)))
+    }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum AnnotationLevel {
     Notice,
@@ -55,7 +222,11 @@ pub enum AnnotationLevel {
     Failure,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Annotation {
     /// Required. The path of the file to add an annotation to. For example,
     /// `assets/css/main.css`.
@@ -70,11 +241,13 @@ pub struct Annotation {
     /// The start column of the annotation. Annotations only support
     /// `start_column` and `end_column` on the same line. Omit this parameter
     /// if `start_line` and `end_line` have different values.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub start_column: Option<u32>,
 
     /// The end column of the annotation. Annotations only support
     /// `start_column` and `end_column` on the same line. Omit this parameter
     /// if `start_line` and `end_line` have different values.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub end_column: Option<u32>,
 
     /// Required. The level of annotation.
@@ -86,13 +259,19 @@ pub struct Annotation {
 
     /// The title that represents the annotation. The maximum size is 255
     /// characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
     /// Raw details about this annotation. The maximum size is 64 KB.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_details: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Image {
     /// Required. The alternative text for the image.
     pub alt: String,
@@ -101,10 +280,15 @@ pub struct Image {
     pub image_url: String,
 
     /// A short image description.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Output {
     /// The title of the check run.
     pub title: String,
@@ -113,6 +297,7 @@ pub struct Output {
     pub summary: String,
 
     /// The details of the check run. This parameter supports Markdown.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
 
     /// Adds information from your analysis to specific lines of code.
@@ -127,14 +312,60 @@ pub struct Output {
     ///
     /// [1]: https://developer.github.com/v3/checks/runs/#update-a-check-run
     /// [2]: https://help.github.com/articles/about-status-checks#checks
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub annotations: Option<Vec<Annotation>>,
 
     /// Adds images to the output displayed in the GitHub pull request UI.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub images: Option<Vec<Image>>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct CheckRunAction {
+impl Output {
+    /// Splits the output into batches of at most `MAX_ANNOTATIONS`
+    /// annotations each, so that they can be sent in successive requests.
+    ///
+    /// The first batch contains the `text` and `images`. All batches contain
+    /// the `title` and `summary`, which are required by GitHub. Annotations
+    /// are ordered by decreasing severity so that failures are sent first in
+    /// case the total is truncated.
+    pub fn into_batches(self) -> Vec<Output> {
+        let mut annotations = self.annotations.unwrap_or_default();
+        annotations.sort_by_key(|a| Reverse(a.annotation_level));
+
+        let mut annotations = annotations.into_iter().peekable();
+        let first: Vec<_> =
+            annotations.by_ref().take(MAX_ANNOTATIONS).collect();
+
+        let mut batches = vec![Output {
+            title: self.title.clone(),
+            summary: self.summary.clone(),
+            text: self.text,
+            annotations: if first.is_empty() { None } else { Some(first) },
+            images: self.images,
+        }];
+
+        while annotations.peek().is_some() {
+            batches.push(Output {
+                title: self.title.clone(),
+                summary: self.summary.clone(),
+                text: None,
+                annotations: Some(
+                    annotations.by_ref().take(MAX_ANNOTATIONS).collect(),
+                ),
+                images: None,
+            });
+        }
+
+        batches
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CheckRunAction<I = String> {
     /// The text to be displayed on a button in the web UI. The maximum size is
     /// 20 characters.
     pub label: String,
@@ -145,24 +376,54 @@ pub struct CheckRunAction {
 
     /// A reference for the action on the integrator's system. The maximum size
     /// is 20 characters.
-    pub identifier: String,
+    ///
+    /// This can be any type that deserializes from a string, such as an
+    /// app-defined enum of the actions it supports.
+    pub identifier: I,
+}
+
+/// The action requested by a user in a `CheckRunEvent`.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequestedAction<I = String> {
+    /// The `identifier` of the `CheckRunAction` that the user clicked.
+    pub identifier: I,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct CheckSuiteId {
-    pub id: u64,
+/// Short info about a check suite.
+///
+/// This used to be called `CheckSuiteId`. That name now refers to the ID
+/// itself.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShortCheckSuite {
+    pub id: CheckSuiteId,
 }
 
 /// A repo associated with a `CheckRun`.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckRunRepo {
-    pub id: u64,
+    pub id: RepoId,
     pub url: String,
     pub name: String,
 }
 
 /// A commit associated with a `CheckRun`.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckRunCommit {
     #[serde(rename = "ref")]
     pub git_ref: String,
@@ -171,19 +432,43 @@ pub struct CheckRunCommit {
 }
 
 /// A pull request associated with a `CheckRun`.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckRunPullRequest {
     pub url: String,
-    pub id: u64,
-    pub number: u64,
+    pub id: PullRequestId,
+    pub number: IssueNumber,
     pub head: CheckRunCommit,
     pub base: CheckRunCommit,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct CheckRun {
+/// The output of a check run, as GitHub sends it back. Unlike [`Output`],
+/// the annotations themselves are not included; they have to be fetched from
+/// `annotations_url`.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CheckRunOutput {
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub text: Option<String>,
+    pub annotations_count: u64,
+    pub annotations_url: String,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CheckRun<I = String> {
     /// The ID of the check run.
-    pub id: u64,
+    pub id: CheckRunId,
 
     /// The name of the check run.
     pub name: String,
@@ -211,9 +496,9 @@ pub struct CheckRun {
     /// The time the check completed.
     pub completed_at: Option<DateTime>,
 
-    pub output: Option<Output>,
+    pub output: Option<CheckRunOutput>,
 
-    pub check_suite: CheckSuiteId,
+    pub check_suite: ShortCheckSuite,
 
     pub app: App,
 
@@ -221,12 +506,16 @@ pub struct CheckRun {
 
     /// Possible further actions the integrator can perform, which a user may
     /// trigger. A maximum of three actions are accepted.
-    pub actions: Option<Vec<CheckRunAction>>,
+    pub actions: Option<Vec<CheckRunAction<I>>>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckSuite {
-    pub id: u64,
+    pub id: CheckSuiteId,
 
     /// The head branch name of the changes are on.
     ///
@@ -261,4 +550,145 @@ pub struct CheckSuite {
     pub pull_requests: Vec<CheckRunPullRequest>,
 
     pub app: App,
+
+    /// The commit at the head of the check suite.
+    pub head_commit: Option<HeadCommit>,
+
+    /// The number of check runs in the check suite, counting only the latest
+    /// run of each check.
+    pub latest_check_runs_count: Option<u32>,
+
+    /// Whether or not the check suite can be re-requested.
+    pub rerequestable: Option<bool>,
+
+    /// Whether or not the check runs in the check suite can be re-requested.
+    pub runs_rerequestable: Option<bool>,
+
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+
+    /// The repository of the check suite. This is only included when the
+    /// check suite is returned by the REST API, not in webhook payloads
+    /// where it is part of the event instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<Repository>,
+}
+
+/// The head commit of a `CheckSuite`.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HeadCommit {
+    pub id: Oid,
+    pub tree_id: Oid,
+    pub message: String,
+    pub timestamp: DateTime,
+    pub author: PushAuthor,
+    pub committer: PushAuthor,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures;
+    use crate::{Event, EventType};
+
+    fn run(status: CheckRunStatus, conclusion: Option<Conclusion>) -> CheckRun {
+        let fixture =
+            fixtures::get(EventType::CheckRun, Some("completed")).unwrap();
+
+        let mut run = match fixture.event() {
+            Event::CheckRun(event) => event.check_run,
+            event => panic!("unexpected event {:?}", event),
+        };
+
+        run.status = status;
+        run.conclusion = conclusion;
+        run
+    }
+
+    fn completed(conclusions: &[Conclusion]) -> Option<Conclusion> {
+        let runs: Vec<_> = conclusions
+            .iter()
+            .map(|&c| run(CheckRunStatus::Completed, Some(c)))
+            .collect();
+
+        let summary = CheckSuiteSummary::from_check_runs(&runs);
+        assert_eq!(summary.status, CheckRunStatus::Completed);
+        summary.conclusion
+    }
+
+    #[test]
+    fn conclusion_precedence() {
+        use Conclusion::*;
+
+        let order = [
+            Skipped,
+            Neutral,
+            Success,
+            Stale,
+            Failure,
+            TimedOut,
+            Cancelled,
+            StartupFailure,
+            ActionRequired,
+        ];
+
+        for (i, &a) in order.iter().enumerate() {
+            assert_eq!(completed(&[a]), Some(a));
+
+            for &b in &order[..i] {
+                assert_eq!(completed(&[a, b]), Some(a), "{:?} vs. {:?}", a, b);
+                assert_eq!(completed(&[b, a]), Some(a), "{:?} vs. {:?}", b, a);
+            }
+        }
+
+        assert_eq!(completed(&[Success, Failure, Success]), Some(Failure));
+    }
+
+    #[test]
+    fn status_precedence() {
+        use CheckRunStatus::*;
+
+        let order =
+            [Completed, Requested, Pending, Waiting, Queued, InProgress];
+
+        for (i, &a) in order.iter().enumerate() {
+            for &b in &order[..=i] {
+                let runs = [run(a, None), run(b, None)];
+                let summary = CheckSuiteSummary::from_check_runs(&runs);
+                assert_eq!(summary.status, a, "{:?} vs. {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_has_no_conclusion() {
+        let runs = [
+            run(CheckRunStatus::Completed, Some(Conclusion::Failure)),
+            run(CheckRunStatus::InProgress, None),
+        ];
+
+        assert_eq!(
+            CheckSuiteSummary::from_check_runs(&runs),
+            CheckSuiteSummary {
+                status: CheckRunStatus::InProgress,
+                conclusion: None,
+            }
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            CheckSuiteSummary::from_check_runs(&[] as &[CheckRun]),
+            CheckSuiteSummary {
+                status: CheckRunStatus::Queued,
+                conclusion: None,
+            }
+        );
+    }
 }
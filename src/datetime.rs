@@ -17,21 +17,100 @@
 // LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
-use chrono;
+use chrono::{Datelike, FixedOffset, SecondsFormat, TimeZone, Utc};
 use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
 
 /// A UTC datetime that can be deserialized as either a string or unix
 /// timestamp.
-#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct DateTime(pub chrono::DateTime<chrono::Utc>);
+///
+/// GitHub uses both forms, sometimes for the same field in different payloads
+/// (e.g., `created_at` of the repository in a `push` event). The form and UTC
+/// offset that a datetime was deserialized from are kept so that it is
+/// serialized the same way. Otherwise, they are ignored: two datetimes are
+/// equal if they refer to the same instant.
+#[derive(Clone)]
+pub struct DateTime(pub chrono::DateTime<Utc>, Format);
+
+/// How a `DateTime` is serialized.
+#[derive(Copy, Clone)]
+enum Format {
+    /// An RFC 3339 string with the given UTC offset and precision. If
+    /// `use_z` is set, a zero offset is written as `Z`.
+    Rfc3339 {
+        offset: FixedOffset,
+        seconds: SecondsFormat,
+        use_z: bool,
+    },
+
+    /// Seconds since the unix epoch.
+    Timestamp,
+}
 
 impl DateTime {
     /// Returns a `DateTime` which corresponds to the current date.
     pub fn now() -> Self {
-        DateTime(chrono::Utc::now())
+        DateTime::from(Utc::now())
+    }
+
+    /// Returns a `DateTime` for the given seconds since the unix epoch. It is
+    /// serialized as an integer. Returns `None` if the timestamp is out of
+    /// range.
+    pub fn from_timestamp(secs: i64) -> Option<Self> {
+        Utc.timestamp_opt(secs, 0)
+            .single()
+            .map(|datetime| DateTime(datetime, Format::Timestamp))
+    }
+}
+
+impl From<chrono::DateTime<Utc>> for DateTime {
+    fn from(datetime: chrono::DateTime<Utc>) -> Self {
+        DateTime::from(datetime.fixed_offset())
+    }
+}
+
+impl From<chrono::DateTime<FixedOffset>> for DateTime {
+    fn from(datetime: chrono::DateTime<FixedOffset>) -> Self {
+        DateTime(
+            datetime.with_timezone(&Utc),
+            Format::Rfc3339 {
+                offset: *datetime.offset(),
+                seconds: SecondsFormat::AutoSi,
+                use_z: true,
+            },
+        )
+    }
+}
+
+impl PartialEq for DateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for DateTime {}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DateTime {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl Hash for DateTime {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
     }
 }
 
@@ -59,6 +138,39 @@ impl Deref for DateTime {
     }
 }
 
+impl Serialize for DateTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            if let Format::Rfc3339 {
+                offset,
+                seconds,
+                use_z,
+            } = self.1
+            {
+                let local = self.0.with_timezone(&offset);
+
+                // RFC 3339 only allows four digit years. Anything else is
+                // serialized as a timestamp instead, unless that would lose
+                // the fractional seconds. chrono writes and parses such
+                // years with a sign (e.g., `+10000-01-01T00:00:00.5Z`).
+                if (0..=9999).contains(&local.year())
+                    || self.0.timestamp_subsec_nanos() != 0
+                {
+                    // Serialize the same way GitHub does (e.g.,
+                    // `2019-05-15T15:20:33Z`).
+                    return serializer
+                        .serialize_str(&local.to_rfc3339_opts(seconds, use_z));
+                }
+            }
+        }
+
+        serializer.serialize_i64(self.0.timestamp())
+    }
+}
+
 impl<'de> Deserialize<'de> for DateTime {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -77,8 +189,36 @@ impl<'de> Deserialize<'de> for DateTime {
             where
                 E: de::Error,
             {
+                let datetime = v
+                    .parse::<chrono::DateTime<FixedOffset>>()
+                    .map_err(|e| E::custom(format!("{}", e)))?;
+
+                // Keep the precision of the fractional seconds, if any
+                // (e.g., `12:00:00.000-07:00`).
+                let digits = v
+                    .find('.')
+                    .map(|i| {
+                        v[i + 1..]
+                            .bytes()
+                            .take_while(u8::is_ascii_digit)
+                            .count()
+                    })
+                    .unwrap_or(0);
+
+                let seconds = match digits {
+                    0 => SecondsFormat::Secs,
+                    1..=3 => SecondsFormat::Millis,
+                    4..=6 => SecondsFormat::Micros,
+                    _ => SecondsFormat::Nanos,
+                };
+
                 Ok(DateTime(
-                    v.parse().map_err(|e| E::custom(format!("{}", e)))?,
+                    datetime.with_timezone(&Utc),
+                    Format::Rfc3339 {
+                        offset: *datetime.offset(),
+                        seconds,
+                        use_z: v.ends_with(['Z', 'z']),
+                    },
                 ))
             }
 
@@ -87,9 +227,8 @@ impl<'de> Deserialize<'de> for DateTime {
                 E: de::Error,
             {
                 use chrono::offset::LocalResult;
-                use chrono::TimeZone;
 
-                match chrono::Utc.timestamp_opt(v, 0) {
+                match Utc.timestamp_opt(v, 0) {
                     LocalResult::None => Err(E::custom(format!(
                         "value is not a legal timestamp: {}",
                         v
@@ -101,7 +240,9 @@ impl<'de> Deserialize<'de> for DateTime {
                             v, min, max
                         )))
                     }
-                    LocalResult::Single(datetime) => Ok(DateTime(datetime)),
+                    LocalResult::Single(datetime) => {
+                        Ok(DateTime(datetime, Format::Timestamp))
+                    }
                 }
             }
 
@@ -109,7 +250,13 @@ impl<'de> Deserialize<'de> for DateTime {
             where
                 E: de::Error,
             {
-                self.visit_i64(v as i64)
+                match i64::try_from(v) {
+                    Ok(v) => self.visit_i64(v),
+                    Err(_) => Err(E::custom(format!(
+                        "value is not a legal timestamp: {}",
+                        v
+                    ))),
+                }
             }
         }
 
@@ -120,3 +267,87 @@ impl<'de> Deserialize<'de> for DateTime {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(json: &str) -> String {
+        let datetime: DateTime = serde_json::from_str(json).unwrap();
+        let output = serde_json::to_string(&datetime).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DateTime>(&output).unwrap(),
+            datetime
+        );
+        output
+    }
+
+    #[test]
+    fn keeps_form() {
+        assert_eq!(round_trip("1557933565"), "1557933565");
+        assert_eq!(
+            round_trip("\"2019-05-15T15:20:33Z\""),
+            "\"2019-05-15T15:20:33Z\""
+        );
+        assert_eq!(
+            round_trip("\"2019-05-15T15:20:30-05:00\""),
+            "\"2019-05-15T15:20:30-05:00\""
+        );
+        assert_eq!(
+            round_trip("\"2023-06-01T12:00:00.000-07:00\""),
+            "\"2023-06-01T12:00:00.000-07:00\""
+        );
+        assert_eq!(
+            round_trip("\"2019-05-15T15:20:33+00:00\""),
+            "\"2019-05-15T15:20:33+00:00\""
+        );
+    }
+
+    #[test]
+    fn equality_ignores_form() {
+        let timestamp: DateTime = serde_json::from_str("1557933633").unwrap();
+        let string: DateTime =
+            serde_json::from_str("\"2019-05-15T10:20:33-05:00\"").unwrap();
+        assert_eq!(timestamp, string);
+    }
+
+    #[test]
+    fn years_outside_rfc3339() {
+        // Years past 9999 and before 0 can't be written as RFC 3339.
+        for &secs in &[300_000_000_000, 253_402_300_800, -62_167_219_201] {
+            let json = secs.to_string();
+            assert_eq!(round_trip(&json), json);
+
+            let datetime = DateTime::from(Utc.timestamp_opt(secs, 0).unwrap());
+            assert_eq!(serde_json::to_string(&datetime).unwrap(), json);
+        }
+
+        // chrono accepts such years in strings as well.
+        assert_eq!(round_trip("\"+10000-01-01T00:00:00Z\""), "253402300800");
+
+        // Fractional seconds don't fit in a timestamp, so they stay strings.
+        for json in &[
+            "\"+10000-01-01T00:00:00.500Z\"",
+            "\"-0001-12-31T23:59:59.250+01:00\"",
+        ] {
+            assert_eq!(round_trip(json), *json);
+        }
+        assert_eq!(
+            round_trip("\"+10000-01-01T00:00:00.5Z\""),
+            "\"+10000-01-01T00:00:00.500Z\""
+        );
+
+        assert_eq!(round_trip("253402300799"), "253402300799");
+        assert_eq!(round_trip("-62167219200"), "-62167219200");
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert!(
+            serde_json::from_str::<DateTime>("9223372036854775807").is_err()
+        );
+        assert!(
+            serde_json::from_str::<DateTime>("18446744073709551615").is_err()
+        );
+    }
+}
@@ -0,0 +1,635 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Unified diffs, as returned by `PullRequest::diff_url`.
+//!
+//! Review comments can be placed either by `position` (the legacy line index
+//! into a file's diff) or by `line` and `side`. The types here map between
+//! the two.
+
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Side;
+
+/// The kind of a line in a hunk.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LineKind {
+    /// An unchanged line (prefixed with a space).
+    Context,
+
+    /// A line that was added (prefixed with `+`).
+    Added,
+
+    /// A line that was removed (prefixed with `-`).
+    Removed,
+}
+
+/// A single line in a hunk.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DiffLine {
+    pub kind: LineKind,
+
+    /// The content of the line without the leading `+`, `-`, or space.
+    pub content: String,
+
+    /// The line number in the old file. This is `None` for added lines.
+    pub old_line: Option<u32>,
+
+    /// The line number in the new file. This is `None` for removed lines.
+    pub new_line: Option<u32>,
+
+    /// The number of lines down from the first hunk header of the file. This
+    /// is the legacy `position` used by review comments.
+    pub position: u32,
+}
+
+impl DiffLine {
+    /// Returns the line number and side a review comment on this line would
+    /// use. Removed lines are on the left side, everything else is on the
+    /// right side. Returns `None` if the line has no line numbers, which
+    /// never happens for parsed diffs.
+    pub fn line_and_side(&self) -> Option<(u32, Side)> {
+        match (self.kind, self.old_line, self.new_line) {
+            (LineKind::Removed, Some(old), _) => Some((old, Side::Left)),
+            (_, _, Some(new)) => Some((new, Side::Right)),
+            (_, Some(old), None) => Some((old, Side::Left)),
+            (_, None, None) => None,
+        }
+    }
+}
+
+/// A contiguous region of changes in a file.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Hunk {
+    /// The first line of the hunk in the old file.
+    pub old_start: u32,
+
+    /// The number of lines of the old file in this hunk.
+    pub old_lines: u32,
+
+    /// The first line of the hunk in the new file.
+    pub new_start: u32,
+
+    /// The number of lines of the new file in this hunk.
+    pub new_lines: u32,
+
+    /// The text following the `@@ ... @@` range, usually the enclosing
+    /// function.
+    pub section: String,
+
+    /// The lines in the hunk.
+    pub lines: Vec<DiffLine>,
+}
+
+/// The changes to a single file.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FileDiff {
+    /// The path of the file before the change. This is `None` if the file
+    /// was added.
+    pub old_path: Option<String>,
+
+    /// The path of the file after the change. This is `None` if the file was
+    /// deleted.
+    pub new_path: Option<String>,
+
+    /// `true` if the file is binary. Binary files have no hunks.
+    pub binary: bool,
+
+    /// The hunks of the file.
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    /// Returns the path of the file, preferring the new path.
+    pub fn path(&self) -> Option<&str> {
+        self.new_path.as_deref().or(self.old_path.as_deref())
+    }
+
+    /// Returns an iterator over all lines in all hunks.
+    pub fn lines(&self) -> impl Iterator<Item = &DiffLine> {
+        self.hunks.iter().flat_map(|h| h.lines.iter())
+    }
+
+    /// Returns the diff line at the given `position`, if any.
+    pub fn line_at(&self, position: u32) -> Option<&DiffLine> {
+        self.lines().find(|l| l.position == position)
+    }
+
+    /// Returns the diff line for the given `line` and `side`, if it is part
+    /// of the diff.
+    pub fn find_line(&self, line: u32, side: Side) -> Option<&DiffLine> {
+        self.lines().find(|l| match side {
+            Side::Left => l.kind != LineKind::Added && l.old_line == Some(line),
+            Side::Right => {
+                l.kind != LineKind::Removed && l.new_line == Some(line)
+            }
+        })
+    }
+
+    /// Converts a `line` and `side` into a legacy `position`.
+    pub fn position(&self, line: u32, side: Side) -> Option<u32> {
+        self.find_line(line, side).map(|l| l.position)
+    }
+
+    /// Converts a legacy `position` into a `line` and `side`.
+    pub fn line_and_side(&self, position: u32) -> Option<(u32, Side)> {
+        self.line_at(position)?.line_and_side()
+    }
+}
+
+/// A parsed unified diff containing any number of files.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Diff {
+    pub files: Vec<FileDiff>,
+}
+
+impl Diff {
+    /// Parses a unified diff, such as the output of `git diff`.
+    ///
+    /// Hunks without a preceding file header, such as the `patch` of a single
+    /// pull request file, are collected into a file without any paths.
+    pub fn parse(s: &str) -> Result<Self, ParseDiffError> {
+        Parser::default().parse(s)
+    }
+
+    /// Returns the diff of the file with the given path. Both the old and new
+    /// paths are considered.
+    pub fn file(&self, path: &str) -> Option<&FileDiff> {
+        self.files
+            .iter()
+            .find(|f| f.new_path.as_deref() == Some(path))
+            .or_else(|| {
+                self.files
+                    .iter()
+                    .find(|f| f.old_path.as_deref() == Some(path))
+            })
+    }
+
+    /// Converts a `line` and `side` in the file at `path` into a legacy
+    /// `position`.
+    pub fn position(&self, path: &str, line: u32, side: Side) -> Option<u32> {
+        self.file(path)?.position(line, side)
+    }
+
+    /// Converts a legacy `position` in the file at `path` into a `line` and
+    /// `side`.
+    pub fn line_and_side(
+        &self,
+        path: &str,
+        position: u32,
+    ) -> Option<(u32, Side)> {
+        self.file(path)?.line_and_side(position)
+    }
+}
+
+impl FromStr for Diff {
+    type Err = ParseDiffError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Diff::parse(s)
+    }
+}
+
+/// An error encountered while parsing a diff.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ParseDiffError {
+    /// The 1-based line of the diff where the error occurred.
+    pub line: usize,
+
+    /// A description of the error.
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseDiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid diff at line {}: {}", self.line, self.message)
+    }
+}
+
+impl error::Error for ParseDiffError {}
+
+/// The hunk currently being parsed.
+struct HunkState {
+    hunk: Hunk,
+    old_line: u32,
+    new_line: u32,
+    old_remaining: u32,
+    new_remaining: u32,
+}
+
+#[derive(Default)]
+struct Parser {
+    files: Vec<FileDiff>,
+    hunk: Option<HunkState>,
+
+    /// The position of the next line in the current file.
+    position: u32,
+
+    /// `true` if the current file has a `---` header.
+    saw_old_path: bool,
+
+    /// The current (1-based) line in the diff.
+    line: usize,
+}
+
+impl Parser {
+    fn error(&self, message: &'static str) -> ParseDiffError {
+        ParseDiffError {
+            line: self.line,
+            message,
+        }
+    }
+
+    fn parse(mut self, s: &str) -> Result<Diff, ParseDiffError> {
+        // Only split on `\n` so that a `\r` at the end of a line is kept as
+        // part of its content.
+        for line in s.split_terminator('\n') {
+            self.line += 1;
+            self.parse_line(line)?;
+        }
+
+        if let Some(state) = &self.hunk {
+            if state.old_remaining > 0 || state.new_remaining > 0 {
+                return Err(self.error("unexpected end of hunk"));
+            }
+        }
+
+        self.finish_hunk();
+
+        Ok(Diff { files: self.files })
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), ParseDiffError> {
+        if let Some(state) = &mut self.hunk {
+            if state.old_remaining > 0 || state.new_remaining > 0 {
+                return self.parse_hunk_line(line);
+            }
+
+            if line.starts_with('\\') {
+                // A "\ No newline at end of file" marker after the last line
+                // of the hunk.
+                self.position += 1;
+                return Ok(());
+            }
+        }
+
+        self.finish_hunk();
+
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let (old, new) = split_git_paths(rest);
+            self.start_file(old, new);
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            // Plain unified diffs have no "diff --git" header, so a "---" line
+            // may also start a new file.
+            let starts_file = match self.files.last() {
+                Some(f) => !f.hunks.is_empty() || f.binary || self.saw_old_path,
+                None => true,
+            };
+
+            if starts_file {
+                self.start_file(None, None);
+            }
+
+            self.saw_old_path = true;
+            self.current_file()?.old_path = parse_path(path, "a/");
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            self.current_file()?.new_path = parse_path(path, "b/");
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            self.current_file()?.old_path = Some(path.to_string());
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            self.current_file()?.new_path = Some(path.to_string());
+        } else if line.starts_with("new file mode ") {
+            self.current_file()?.old_path = None;
+        } else if line.starts_with("deleted file mode ") {
+            self.current_file()?.new_path = None;
+        } else if line.starts_with("Binary files ")
+            || line == "GIT binary patch"
+        {
+            self.current_file()?.binary = true;
+        } else if line.starts_with("@@ ") {
+            self.start_hunk(line)?;
+        }
+
+        // Anything else (e.g., "index", "similarity index", mode changes, or
+        // binary patch data) is ignored.
+        Ok(())
+    }
+
+    fn parse_hunk_line(&mut self, line: &str) -> Result<(), ParseDiffError> {
+        let (kind, content) = match line.chars().next() {
+            Some(' ') => (LineKind::Context, &line[1..]),
+            Some('+') => (LineKind::Added, &line[1..]),
+            Some('-') => (LineKind::Removed, &line[1..]),
+            Some('\\') => {
+                self.position += 1;
+                return Ok(());
+            }
+            // Some tools strip the trailing space from empty context lines.
+            None => (LineKind::Context, ""),
+            Some(_) => return Err(self.error("invalid line in hunk")),
+        };
+
+        let position = self.position;
+        self.position += 1;
+
+        let state = self.hunk.as_mut().unwrap();
+
+        let (old_line, new_line) = match kind {
+            LineKind::Context => (Some(state.old_line), Some(state.new_line)),
+            LineKind::Added => (None, Some(state.new_line)),
+            LineKind::Removed => (Some(state.old_line), None),
+        };
+
+        if old_line.is_some() {
+            if state.old_remaining == 0 {
+                return Err(self.error("too many lines in hunk"));
+            }
+
+            state.old_line += 1;
+            state.old_remaining -= 1;
+        }
+
+        if new_line.is_some() {
+            if state.new_remaining == 0 {
+                return Err(self.error("too many lines in hunk"));
+            }
+
+            state.new_line += 1;
+            state.new_remaining -= 1;
+        }
+
+        state.hunk.lines.push(DiffLine {
+            kind,
+            content: content.to_string(),
+            old_line,
+            new_line,
+            position,
+        });
+
+        Ok(())
+    }
+
+    fn current_file(&mut self) -> Result<&mut FileDiff, ParseDiffError> {
+        let error = self.error("expected a file header");
+        self.files.last_mut().ok_or(error)
+    }
+
+    fn start_file(&mut self, old: Option<String>, new: Option<String>) {
+        self.files.push(FileDiff {
+            old_path: old,
+            new_path: new,
+            binary: false,
+            hunks: Vec::new(),
+        });
+
+        self.position = 0;
+        self.saw_old_path = false;
+    }
+
+    fn start_hunk(&mut self, line: &str) -> Result<(), ParseDiffError> {
+        let error = self.error("invalid hunk header");
+
+        let (ranges, section) =
+            line[3..].split_once(" @@").ok_or_else(|| error.clone())?;
+
+        let (old, new) = ranges.split_once(' ').ok_or_else(|| error.clone())?;
+
+        let (old_start, old_lines) = old
+            .strip_prefix('-')
+            .and_then(parse_range)
+            .ok_or_else(|| error.clone())?;
+        let (new_start, new_lines) =
+            new.strip_prefix('+').and_then(parse_range).ok_or(error)?;
+
+        // An empty range starts at the line *before* the hunk.
+        let old_line = old_start.max(1);
+        let new_line = new_start.max(1);
+
+        // Counting lines must not run past the last possible line number.
+        if old_line.checked_add(old_lines).is_none()
+            || new_line.checked_add(new_lines).is_none()
+        {
+            return Err(self.error("hunk range is too large"));
+        }
+
+        if self.files.is_empty() {
+            self.start_file(None, None);
+        }
+
+        // The first hunk header isn't counted, but subsequent ones are.
+        if self.files.last().is_some_and(|f| !f.hunks.is_empty()) {
+            self.position += 1;
+        } else {
+            self.position = 1;
+        }
+
+        self.hunk = Some(HunkState {
+            hunk: Hunk {
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                section: section.trim_start().to_string(),
+                lines: Vec::new(),
+            },
+            old_line,
+            new_line,
+            old_remaining: old_lines,
+            new_remaining: new_lines,
+        });
+
+        Ok(())
+    }
+
+    fn finish_hunk(&mut self) {
+        if let Some(state) = self.hunk.take() {
+            if let Some(file) = self.files.last_mut() {
+                file.hunks.push(state.hunk);
+            }
+        }
+    }
+}
+
+/// Parses a hunk range such as `12,5` or `12`.
+fn parse_range(s: &str) -> Option<(u32, u32)> {
+    match s.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((s.parse().ok()?, 1)),
+    }
+}
+
+/// Parses the path from a `---` or `+++` line.
+fn parse_path(s: &str, prefix: &str) -> Option<String> {
+    // Some tools append a tab followed by a timestamp.
+    let s = s.split('\t').next().unwrap_or(s);
+
+    if s == "/dev/null" {
+        return None;
+    }
+
+    Some(s.strip_prefix(prefix).unwrap_or(s).to_string())
+}
+
+/// Splits the paths of a `diff --git a/old b/new` header. The paths may be
+/// overridden later by `---`, `+++`, or rename headers, which are unambiguous.
+fn split_git_paths(s: &str) -> (Option<String>, Option<String>) {
+    // If both paths are the same, the header is `a/<path> b/<path>`, which
+    // is unambiguous even if the path contains " b/".
+    let s = s.strip_prefix("a/").unwrap_or(s);
+
+    if s.len() >= 3 && s.len() % 2 == 1 {
+        let mid = (s.len() - 3) / 2;
+
+        if s.get(mid..mid + 3) == Some(" b/") && s[..mid] == s[mid + 3..] {
+            let path = s[..mid].to_string();
+            return (Some(path.clone()), Some(path));
+        }
+    }
+
+    match s.split_once(" b/") {
+        Some((old, new)) => (Some(old.to_string()), Some(new.to_string())),
+        None => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@ mod a;
+ line 1
+-line 2
++line two
++line 2.5
+ line 3
+@@ -10,2 +11,2 @@ fn main() {
+ line 10
+-line 11
++line eleven
+diff --git a/old.txt b/new.txt
+similarity index 90%
+rename from old.txt
+rename to new.txt
+--- a/old.txt
++++ b/new.txt
+@@ -1 +1 @@
+-a
+\\ No newline at end of file
++b
+\\ No newline at end of file
+";
+
+    #[test]
+    fn multiple_hunks() {
+        let diff = Diff::parse(DIFF).unwrap();
+        let file = diff.file("src/lib.rs").unwrap();
+
+        assert_eq!(file.hunks.len(), 2);
+        assert_eq!(file.hunks[1].old_start, 10);
+        assert_eq!(file.hunks[1].new_start, 11);
+        assert_eq!(file.hunks[1].section, "fn main() {");
+
+        // The second hunk header takes up a position.
+        let positions: Vec<_> = file.lines().map(|l| l.position).collect();
+        assert_eq!(positions, [1, 2, 3, 4, 5, 7, 8, 9]);
+
+        let line = file.line_at(9).unwrap();
+        assert_eq!(line.content, "line eleven");
+        assert_eq!((line.old_line, line.new_line), (None, Some(12)));
+    }
+
+    #[test]
+    fn positions_across_hunks() {
+        let diff = Diff::parse(DIFF).unwrap();
+
+        assert_eq!(diff.position("src/lib.rs", 2, Side::Left), Some(2));
+        assert_eq!(diff.position("src/lib.rs", 3, Side::Right), Some(4));
+        assert_eq!(diff.position("src/lib.rs", 11, Side::Left), Some(8));
+        assert_eq!(diff.position("src/lib.rs", 12, Side::Right), Some(9));
+        assert_eq!(diff.position("src/lib.rs", 5, Side::Right), None);
+
+        assert_eq!(diff.line_and_side("src/lib.rs", 8), Some((11, Side::Left)));
+        assert_eq!(
+            diff.line_and_side("src/lib.rs", 7),
+            Some((11, Side::Right))
+        );
+        assert_eq!(diff.line_and_side("src/lib.rs", 6), None);
+    }
+
+    #[test]
+    fn no_newline_at_end_of_file() {
+        let diff = Diff::parse(DIFF).unwrap();
+        let file = diff.file("new.txt").unwrap();
+
+        // Each marker takes up a position.
+        let lines: Vec<_> = file
+            .lines()
+            .map(|l| (l.kind, l.content.as_str(), l.position))
+            .collect();
+        assert_eq!(
+            lines,
+            [(LineKind::Removed, "a", 1), (LineKind::Added, "b", 3)]
+        );
+    }
+
+    #[test]
+    fn renames() {
+        let diff = Diff::parse(DIFF).unwrap();
+        assert_eq!(diff.files.len(), 2);
+
+        let file = &diff.files[1];
+        assert_eq!(file.old_path.as_deref(), Some("old.txt"));
+        assert_eq!(file.new_path.as_deref(), Some("new.txt"));
+        assert_eq!(diff.file("old.txt"), Some(file));
+        assert_eq!(diff.file("new.txt"), Some(file));
+    }
+
+    #[test]
+    fn carriage_returns() {
+        let diff = Diff::parse("@@ -1,2 +1,2 @@\n a\r\n-b\r\n+c\r\n").unwrap();
+        let contents: Vec<_> =
+            diff.files[0].lines().map(|l| l.content.as_str()).collect();
+        assert_eq!(contents, ["a\r", "b\r", "c\r"]);
+    }
+
+    #[test]
+    fn ranges_past_the_last_line() {
+        let error =
+            Diff::parse("@@ -4294967295,2 +1,2 @@\n a\n a\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "hunk range is too large");
+
+        let error =
+            Diff::parse("@@ -1,2 +4294967295,2 @@\n a\n a\n").unwrap_err();
+        assert_eq!(error.message, "hunk range is too large");
+
+        // The last line of the hunk may be the largest line number.
+        let diff = Diff::parse("@@ -4294967294,1 +1 @@\n a\n").unwrap();
+        let line = diff.files[0].lines().next().unwrap();
+        assert_eq!(line.old_line, Some(u32::MAX - 1));
+    }
+}
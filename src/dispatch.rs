@@ -0,0 +1,277 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Request bodies for triggering repository and workflow dispatches.
+
+use std::cmp::Ordering;
+use std::ops;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+/// An arbitrary JSON `client_payload`.
+///
+/// This is a `serde_json::Value` that can also be ordered, so that events
+/// containing it can still be sorted. The generated types use it for other
+/// free-form JSON as well (e.g., `Deployment::payload`). Values of different
+/// types are ordered `null`, booleans, numbers, strings, arrays, then objects.
+#[derive(
+    Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, Hash,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct ClientPayload(pub Value);
+
+impl From<Value> for ClientPayload {
+    fn from(value: Value) -> Self {
+        ClientPayload(value)
+    }
+}
+
+impl ops::Deref for ClientPayload {
+    type Target = Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ops::DerefMut for ClientPayload {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl PartialOrd for ClientPayload {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ClientPayload {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_values(&self.0, &other.0)
+    }
+}
+
+fn cmp_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => cmp_numbers(a, b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => {
+            for (a, b) in a.iter().zip(b) {
+                match cmp_values(a, b) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+            }
+
+            a.len().cmp(&b.len())
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            // Compare the entries sorted by key, regardless of the order
+            // that the map keeps them in.
+            let mut a: Vec<_> = a.iter().collect();
+            let mut b: Vec<_> = b.iter().collect();
+            a.sort_by_key(|(k, _)| *k);
+            b.sort_by_key(|(k, _)| *k);
+
+            for ((ka, va), (kb, vb)) in a.iter().zip(&b) {
+                match ka.cmp(kb).then_with(|| cmp_values(va, vb)) {
+                    Ordering::Equal => {}
+                    ordering => return ordering,
+                }
+            }
+
+            a.len().cmp(&b.len())
+        }
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Integers are ordered before floats since, like `PartialEq` on `Number`,
+/// an integer is never equal to a float.
+fn cmp_numbers(a: &Number, b: &Number) -> Ordering {
+    fn integer(n: &Number) -> Option<i128> {
+        n.as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+    }
+
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => {
+            // JSON numbers are never NaN.
+            let (a, b) = (a.as_f64(), b.as_f64());
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+    }
+}
+
+/// The request body for creating a repository dispatch event.
+///
+/// This is sent to `POST /repos/{owner}/{repo}/dispatches` and results in a
+/// `RepositoryDispatchEvent` with the same `client_payload` type.
+///
+/// `P` defaults to `ClientPayload` rather than `serde_json::Value` for the same
+/// reason as `RepositoryDispatchEvent`: it can be ordered and hashed. Use
+/// `RepositoryDispatch<serde_json::Value>` to send a plain `Value`.
+///
+/// See "Create a repository dispatch event" in
+/// https://docs.github.com/rest/repos/repos
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RepositoryDispatch<P = ClientPayload> {
+    /// A custom webhook event name. This becomes the `action` of the
+    /// resulting `RepositoryDispatchEvent`. The maximum size is 100
+    /// characters.
+    pub event_type: String,
+
+    /// Extra information about the webhook event that is passed through to
+    /// the `RepositoryDispatchEvent`. The payload can include a maximum of 10
+    /// top-level properties.
+    pub client_payload: P,
+}
+
+impl<P> RepositoryDispatch<P> {
+    pub fn new<S>(event_type: S, client_payload: P) -> Self
+    where
+        S: Into<String>,
+    {
+        RepositoryDispatch {
+            event_type: event_type.into(),
+            client_payload,
+        }
+    }
+}
+
+/// The request body for manually triggering a GitHub Actions workflow run.
+///
+/// This is sent to
+/// `POST /repos/{owner}/{repo}/actions/workflows/{workflow_id}/dispatches`.
+///
+/// See "Create a workflow dispatch event" in
+/// https://docs.github.com/rest/actions/workflows
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkflowDispatch<I = serde_json::Map<String, serde_json::Value>> {
+    /// The git reference for the workflow. The reference can be a branch or
+    /// tag name.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+
+    /// Input keys and values configured in the workflow file. The maximum
+    /// number of properties is 10. Any default properties configured in the
+    /// workflow file will be used when inputs are omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<I>,
+}
+
+impl<I> WorkflowDispatch<I> {
+    /// Creates a workflow dispatch without any inputs.
+    pub fn new<S>(git_ref: S) -> Self
+    where
+        S: Into<String>,
+    {
+        WorkflowDispatch {
+            git_ref: git_ref.into(),
+            inputs: None,
+        }
+    }
+
+    /// Creates a workflow dispatch with the given inputs.
+    pub fn with_inputs<S>(git_ref: S, inputs: I) -> Self
+    where
+        S: Into<String>,
+    {
+        WorkflowDispatch {
+            git_ref: git_ref.into(),
+            inputs: Some(inputs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn client_payload_order() {
+        let ordered = [
+            json!(null),
+            json!(false),
+            json!(true),
+            json!(-1),
+            json!(0),
+            json!(u64::MAX),
+            json!(-0.5),
+            json!(0.5),
+            json!(""),
+            json!("a"),
+            json!([]),
+            json!([1]),
+            json!([1, 2]),
+            json!([2]),
+            json!({}),
+            json!({"a": 1}),
+            json!({"a": 1, "b": 1}),
+            json!({"a": 2}),
+            json!({"b": 1}),
+        ];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                let a = ClientPayload(a.clone());
+                let b = ClientPayload(b.clone());
+                assert_eq!(a.cmp(&b), i.cmp(&j), "{:?} vs. {:?}", a, b);
+                assert_eq!(a == b, i == j);
+            }
+        }
+    }
+
+    #[test]
+    fn client_payload_is_transparent() {
+        let value = json!({"a": [1, "b"]});
+        let payload: ClientPayload =
+            serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(payload, ClientPayload(value.clone()));
+        assert_eq!(serde_json::to_value(&payload).unwrap(), value);
+    }
+}
@@ -0,0 +1,1008 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Detection of drift between GitHub's payloads and these types.
+//!
+//! Fields that a type doesn't know about are silently ignored when
+//! deserializing, so GitHub adding a field goes unnoticed. Likewise, a field
+//! that GitHub stops sending is usually just `None`. A [`Report`] records both
+//! of these cases for every struct that gets deserialized, keyed by the name of
+//! the struct and field (e.g., `PullRequest.auto_merge`).
+//!
+//! Running a directory of captured webhook deliveries through a report shows
+//! where the types have fallen behind:
+//!
+//! ```no_run
+//! use std::fs;
+//!
+//! use github_types::{drift::Report, EventType};
+//!
+//! let mut report = Report::new();
+//!
+//! // Deliveries are saved as `deliveries/<event type>/<delivery id>.json`.
+//! for dir in fs::read_dir("deliveries")? {
+//!     let dir = dir?.path();
+//!     let event_type: EventType =
+//!         dir.file_name().unwrap().to_str().unwrap().parse()?;
+//!
+//!     for file in fs::read_dir(&dir)? {
+//!         let json = fs::read_to_string(file?.path())?;
+//!         report.decode(event_type, &json)?;
+//!     }
+//! }
+//!
+//! print!("{}", report);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Struct variants are reported by the name of the enum and the name of the
+//! variant in the payload (e.g., `Change::renamed.from`).
+//!
+//! Only values that are deserialized directly are inspected. Serde buffers the
+//! contents of internally tagged enums, untagged enums and `#[serde(flatten)]`
+//! fields before deserializing them, so the structs inside them are not
+//! checked. The struct variants of adjacently tagged enums are deserialized
+//! without their field names, so they are not checked either.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::mem;
+use std::ops::AddAssign;
+
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess,
+    SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::{Event, EventType};
+
+/// A record of the fields that differed between the payloads and the types
+/// they were deserialized into.
+///
+/// Structs inside internally tagged enums, untagged enums and flattened
+/// fields are not checked, because serde buffers them before they are
+/// deserialized. Neither are the struct variants of adjacently tagged enums.
+/// See the [module documentation](self).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Report {
+    /// The number of times each struct was deserialized.
+    structs: BTreeMap<String, usize>,
+
+    /// Fields that were present in a payload, but unknown to the struct.
+    unknown: BTreeMap<(String, String), usize>,
+
+    /// Fields known to the struct that were absent from a payload.
+    missing: BTreeMap<(String, &'static str), usize>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the JSON payload of a webhook, given the event type from its
+    /// `X-GitHub-Event` header, and records any drift in this report.
+    pub fn decode(
+        &mut self,
+        event_type: EventType,
+        json: &str,
+    ) -> serde_json::Result<Event> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let event = self.track(|report| {
+            Event::deserialize_payload(
+                event_type,
+                Tracker::new(&mut deserializer, report),
+            )
+        })?;
+        deserializer.end()?;
+        Ok(event)
+    }
+
+    /// Deserializes any type and records any drift in this report. This is
+    /// useful for responses from the REST API.
+    pub fn deserialize<'de, T, D>(
+        &mut self,
+        deserializer: D,
+    ) -> Result<T, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        self.track(|report| T::deserialize(Tracker::new(deserializer, report)))
+    }
+
+    fn track<F, T>(&mut self, f: F) -> T
+    where
+        F: FnOnce(&RefCell<Report>) -> T,
+    {
+        let report = RefCell::new(mem::take(self));
+        let result = f(&report);
+        *self = report.into_inner();
+        result
+    }
+
+    /// Returns `true` if no drift was found.
+    pub fn is_empty(&self) -> bool {
+        self.unknown.is_empty() && self.missing.is_empty()
+    }
+
+    /// Returns the number of times a struct was deserialized. Struct variants
+    /// are named `Enum::variant`, using the name of the variant in the
+    /// payload.
+    pub fn count(&self, type_name: &str) -> usize {
+        self.structs.get(type_name).copied().unwrap_or(0)
+    }
+
+    /// Returns the fields that were present in a payload, but unknown to the
+    /// struct, along with the number of times each was seen.
+    pub fn unknown_fields(&self) -> impl Iterator<Item = (String, usize)> + '_ {
+        self.unknown
+            .iter()
+            .map(|((ty, field), count)| (format!("{}.{}", ty, field), *count))
+    }
+
+    /// Returns the fields known to the struct that were absent from a
+    /// payload, along with the number of times each was absent.
+    pub fn missing_fields(&self) -> impl Iterator<Item = (String, usize)> + '_ {
+        self.missing
+            .iter()
+            .map(|((ty, field), count)| (format!("{}.{}", ty, field), *count))
+    }
+
+    fn record_struct(&mut self, strukt: Struct) {
+        *self.structs.entry(strukt.to_string()).or_insert(0) += 1;
+    }
+
+    fn record_unknown(&mut self, strukt: Struct, field: String) {
+        *self.unknown.entry((strukt.to_string(), field)).or_insert(0) += 1;
+    }
+
+    fn record_missing(&mut self, strukt: Struct, field: &'static str) {
+        *self.missing.entry((strukt.to_string(), field)).or_insert(0) += 1;
+    }
+}
+
+impl AddAssign for Report {
+    fn add_assign(&mut self, other: Self) {
+        for (name, count) in other.structs {
+            *self.structs.entry(name).or_insert(0) += count;
+        }
+
+        for (key, count) in other.unknown {
+            *self.unknown.entry(key).or_insert(0) += count;
+        }
+
+        for (key, count) in other.missing {
+            *self.missing.entry(key).or_insert(0) += count;
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.unknown.is_empty() {
+            writeln!(f, "Unknown fields:")?;
+
+            for ((ty, field), count) in &self.unknown {
+                writeln!(
+                    f,
+                    "    {}.{} ({} of {})",
+                    ty,
+                    field,
+                    count,
+                    self.count(ty)
+                )?;
+            }
+        }
+
+        if !self.missing.is_empty() {
+            writeln!(f, "Missing fields:")?;
+
+            for ((ty, field), count) in &self.missing {
+                writeln!(
+                    f,
+                    "    {}.{} ({} of {})",
+                    ty,
+                    field,
+                    count,
+                    self.count(ty)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A struct that is being deserialized.
+#[derive(Copy, Clone)]
+struct Struct {
+    name: &'static str,
+
+    /// The variant, if this is a struct variant of the enum `name`.
+    variant: Option<&'static str>,
+
+    fields: &'static [&'static str],
+}
+
+impl fmt::Display for Struct {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.variant {
+            Some(variant) => write!(f, "{}::{}", self.name, variant),
+            None => f.write_str(self.name),
+        }
+    }
+}
+
+/// An enum that is being deserialized.
+#[derive(Copy, Clone)]
+struct Enum {
+    name: &'static str,
+    variants: &'static [&'static str],
+}
+
+/// Wraps a deserializer so that every struct deserialized through it is
+/// checked against the fields in the payload.
+struct Tracker<'a, D> {
+    inner: D,
+    report: &'a RefCell<Report>,
+
+    /// Where to store the string that gets deserialized. This is used to find
+    /// out the names of keys.
+    key: Option<&'a RefCell<Option<String>>>,
+}
+
+impl<'a, D> Tracker<'a, D> {
+    fn new(inner: D, report: &'a RefCell<Report>) -> Self {
+        Tracker {
+            inner,
+            report,
+            key: None,
+        }
+    }
+
+    fn wrap<V>(&self, visitor: V) -> Wrap<'a, V> {
+        Wrap {
+            visitor,
+            report: self.report,
+            key: self.key,
+            strukt: None,
+            enumeration: None,
+        }
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let visitor = self.wrap(visitor);
+                self.inner.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, D> Deserializer<'de> for Tracker<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16
+        deserialize_i32 deserialize_i64 deserialize_i128 deserialize_u8
+        deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_option deserialize_unit deserialize_seq deserialize_map
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.deserialize_newtype_struct(name, visitor)
+    }
+
+    fn deserialize_tuple<V>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+        self.inner.deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut visitor = self.wrap(visitor);
+        visitor.strukt = Some(Struct {
+            name,
+            variant: None,
+            fields,
+        });
+        self.inner.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut visitor = self.wrap(visitor);
+        visitor.enumeration = Some(Enum { name, variants });
+        self.inner.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+/// Wraps a visitor so that anything nested inside of the value is also
+/// tracked.
+struct Wrap<'a, V> {
+    visitor: V,
+    report: &'a RefCell<Report>,
+    key: Option<&'a RefCell<Option<String>>>,
+
+    /// The struct being visited, if any.
+    strukt: Option<Struct>,
+
+    /// The enum being visited, if any.
+    enumeration: Option<Enum>,
+}
+
+impl<'a, V> Wrap<'a, V> {
+    fn capture(&self, key: &str) {
+        if let Some(cell) = self.key {
+            *cell.borrow_mut() = Some(key.to_owned());
+        }
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<V::Value, E>
+            where
+                E: de::Error,
+            {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'a, 'de, V> Visitor<'de> for Wrap<'a, V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.visitor.expecting(f)
+    }
+
+    forward_visit! {
+        visit_bool: bool,
+        visit_i8: i8,
+        visit_i16: i16,
+        visit_i32: i32,
+        visit_i64: i64,
+        visit_i128: i128,
+        visit_u8: u8,
+        visit_u16: u16,
+        visit_u32: u32,
+        visit_u64: u64,
+        visit_u128: u128,
+        visit_f32: f32,
+        visit_f64: f64,
+        visit_char: char,
+        visit_bytes: &[u8],
+        visit_borrowed_bytes: &'de [u8],
+        visit_byte_buf: Vec<u8>,
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.capture(v);
+        self.visitor.visit_str(v)
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.capture(v);
+        self.visitor.visit_borrowed_str(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.capture(&v);
+        self.visitor.visit_string(v)
+    }
+
+    fn visit_none<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_none()
+    }
+
+    fn visit_unit<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.visitor.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor
+            .visit_some(Tracker::new(deserializer, self.report))
+    }
+
+    fn visit_newtype_struct<D>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.visitor
+            .visit_newtype_struct(Tracker::new(deserializer, self.report))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<V::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        self.visitor.visit_seq(SeqWrap {
+            inner: seq,
+            report: self.report,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<V::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let strukt = match self.strukt {
+            Some(strukt) => strukt,
+            None => {
+                return self.visitor.visit_map(MapWrap {
+                    inner: map,
+                    report: self.report,
+                    strukt: None,
+                });
+            }
+        };
+
+        let seen = RefCell::new(BTreeSet::new());
+
+        let value = self.visitor.visit_map(MapWrap {
+            inner: map,
+            report: self.report,
+            strukt: Some((strukt, &seen)),
+        })?;
+
+        let seen = seen.into_inner();
+        let mut report = self.report.borrow_mut();
+
+        report.record_struct(strukt);
+
+        for field in strukt.fields {
+            if !seen.contains(*field) {
+                report.record_missing(strukt, field);
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<V::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        self.visitor.visit_enum(EnumWrap {
+            inner: data,
+            report: self.report,
+            enumeration: self.enumeration,
+        })
+    }
+}
+
+/// Wraps a seed so that the value it deserializes is tracked.
+struct SeedWrap<'a, S> {
+    seed: S,
+    report: &'a RefCell<Report>,
+    key: Option<&'a RefCell<Option<String>>>,
+}
+
+impl<'a, 'de, S> DeserializeSeed<'de> for SeedWrap<'a, S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<S::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.seed.deserialize(Tracker {
+            inner: deserializer,
+            report: self.report,
+            key: self.key,
+        })
+    }
+}
+
+struct SeqWrap<'a, A> {
+    inner: A,
+    report: &'a RefCell<Report>,
+}
+
+impl<'a, 'de, A> SeqAccess<'de> for SeqWrap<'a, A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.next_element_seed(SeedWrap {
+            seed,
+            report: self.report,
+            key: None,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct MapWrap<'a, A> {
+    inner: A,
+    report: &'a RefCell<Report>,
+
+    /// The struct being deserialized and the keys seen so far, if any.
+    strukt: Option<(Struct, &'a RefCell<BTreeSet<String>>)>,
+}
+
+impl<'a, 'de, A> MapAccess<'de> for MapWrap<'a, A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let (strukt, seen) = match self.strukt {
+            Some(strukt) => strukt,
+            None => {
+                return self.inner.next_key_seed(SeedWrap {
+                    seed,
+                    report: self.report,
+                    key: None,
+                });
+            }
+        };
+
+        let key = RefCell::new(None);
+
+        let value = self.inner.next_key_seed(SeedWrap {
+            seed,
+            report: self.report,
+            key: Some(&key),
+        })?;
+
+        if let Some(key) = key.into_inner() {
+            if !strukt.fields.contains(&key.as_str()) {
+                self.report.borrow_mut().record_unknown(strukt, key.clone());
+            }
+
+            seen.borrow_mut().insert(key);
+        }
+
+        Ok(value)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.inner.next_value_seed(SeedWrap {
+            seed,
+            report: self.report,
+            key: None,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+struct EnumWrap<'a, A> {
+    inner: A,
+    report: &'a RefCell<Report>,
+
+    /// The enum being deserialized, if known.
+    enumeration: Option<Enum>,
+}
+
+impl<'a, 'de, A> EnumAccess<'de> for EnumWrap<'a, A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = VariantWrap<'a, A::Variant>;
+
+    fn variant_seed<V>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let key = RefCell::new(None);
+
+        let (value, variant) = self.inner.variant_seed(SeedWrap {
+            seed,
+            report: self.report,
+            key: Some(&key),
+        })?;
+
+        // Find the static name of the variant. This is unknown if the variant
+        // was given by index.
+        let name = self.enumeration.and_then(|e| {
+            let key = key.into_inner()?;
+            let variant = e.variants.iter().find(|v| **v == key)?;
+            Some((e.name, *variant))
+        });
+
+        Ok((
+            value,
+            VariantWrap {
+                inner: variant,
+                report: self.report,
+                name,
+            },
+        ))
+    }
+}
+
+struct VariantWrap<'a, A> {
+    inner: A,
+    report: &'a RefCell<Report>,
+
+    /// The names of the enum and variant, if known.
+    name: Option<(&'static str, &'static str)>,
+}
+
+impl<'a, 'de, A> VariantAccess<'de> for VariantWrap<'a, A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.inner.newtype_variant_seed(SeedWrap {
+            seed,
+            report: self.report,
+            key: None,
+        })
+    }
+
+    fn tuple_variant<V>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.inner.tuple_variant(
+            len,
+            Wrap {
+                visitor,
+                report: self.report,
+                key: None,
+                strukt: None,
+                enumeration: None,
+            },
+        )
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let strukt = self.name.map(|(name, variant)| Struct {
+            name,
+            variant: Some(variant),
+            fields,
+        });
+
+        self.inner.struct_variant(
+            fields,
+            Wrap {
+                visitor,
+                report: self.report,
+                key: None,
+                strukt,
+                enumeration: None,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    use crate::{ShortRepo, Team};
+
+    #[test]
+    fn unknown_fields() {
+        let mut report = Report::new();
+        let repo: ShortRepo = report
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#"{"id": 1, "name": "b", "full_name": "a/b",
+                    "private": false, "visibility": "public"}"#,
+            ))
+            .unwrap();
+
+        assert_eq!(repo.full_name, "a/b");
+        assert_eq!(report.count("ShortRepo"), 1);
+        assert_eq!(
+            report.unknown_fields().collect::<Vec<_>>(),
+            vec![("ShortRepo.visibility".to_string(), 1)]
+        );
+        assert_eq!(report.missing_fields().count(), 0);
+    }
+
+    #[test]
+    fn missing_fields() {
+        let mut report = Report::new();
+        let _: Option<Vec<Team>> = report
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#"[{"id": 1, "url": "", "html_url": "", "name": "a",
+                     "slug": "a", "permission": "pull", "members_url": "",
+                     "repositories_url": ""}]"#,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            report.missing_fields().collect::<Vec<_>>(),
+            vec![
+                ("Team.description".to_string(), 1),
+                ("Team.node_id".to_string(), 1),
+                ("Team.privacy".to_string(), 1),
+            ]
+        );
+        assert_eq!(report.unknown_fields().count(), 0);
+    }
+
+    #[test]
+    fn nested_structs() {
+        let json = include_str!("../fixtures/ping.json");
+
+        let mut report = Report::new();
+        let event = report.decode(EventType::Ping, json).unwrap();
+
+        assert_eq!(event, Event::from_json(EventType::Ping, json).unwrap());
+        assert_eq!(report.count("PingEvent"), 1);
+        assert_eq!(report.count("Repository"), 1);
+        assert_eq!(report.count("User"), 2);
+        assert!(report
+            .unknown_fields()
+            .any(|(path, _)| path == "User.node_id"));
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Change {
+        Renamed { from: String, to: Option<String> },
+        Deleted,
+    }
+
+    #[test]
+    fn struct_variants() {
+        let mut report = Report::new();
+        let changes: Vec<Change> = report
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#"[{"renamed": {"from": "a", "by": "b"}}, "deleted"]"#,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                Change::Renamed {
+                    from: "a".to_string(),
+                    to: None
+                },
+                Change::Deleted
+            ]
+        );
+        assert_eq!(report.count("Change::renamed"), 1);
+        assert_eq!(
+            report.unknown_fields().collect::<Vec<_>>(),
+            vec![("Change::renamed.by".to_string(), 1)]
+        );
+        assert_eq!(
+            report.missing_fields().collect::<Vec<_>>(),
+            vec![("Change::renamed.to".to_string(), 1)]
+        );
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(tag = "type", content = "value")]
+    enum Adjacent {
+        A(ShortRepo),
+        B { x: u32 },
+    }
+
+    #[derive(Deserialize, Debug)]
+    #[serde(tag = "type")]
+    enum Internal {
+        A(ShortRepo),
+        B { x: u32 },
+    }
+
+    #[test]
+    fn tagged_enums() {
+        let mut report = Report::new();
+        let values: Vec<Adjacent> = report
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#"[{"type": "A", "value": {"id": 1, "name": "b",
+                     "full_name": "a/b", "private": false, "public": true}},
+                    {"type": "B", "value": {"x": 1, "y": 2}}]"#,
+            ))
+            .unwrap();
+
+        assert!(matches!(&values[0], Adjacent::A(r) if r.full_name == "a/b"));
+        assert!(matches!(values[1], Adjacent::B { x: 1 }));
+
+        // The field names of adjacently tagged struct variants aren't known.
+        assert_eq!(
+            report.unknown_fields().collect::<Vec<_>>(),
+            vec![("ShortRepo.public".to_string(), 1)]
+        );
+
+        // The contents of internally tagged enums are buffered, so nothing is
+        // checked.
+        let mut report = Report::new();
+        let values: Vec<Internal> = report
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#"[{"type": "A", "id": 1, "name": "b", "full_name": "a/b",
+                     "private": false, "public": true},
+                    {"type": "B", "x": 1, "y": 2}]"#,
+            ))
+            .unwrap();
+
+        assert!(matches!(&values[0], Internal::A(r) if r.full_name == "a/b"));
+        assert!(matches!(values[1], Internal::B { x: 1 }));
+
+        assert!(report.is_empty());
+        assert_eq!(report.count("ShortRepo"), 0);
+    }
+
+    #[test]
+    fn merge() {
+        let json = include_str!("../fixtures/ping.json");
+
+        let mut a = Report::new();
+        a.decode(EventType::Ping, json).unwrap();
+
+        let mut b = a.clone();
+        b += a.clone();
+
+        assert_eq!(b.count("User"), 2 * a.count("User"));
+        assert_eq!(
+            b.to_string().lines().count(),
+            a.to_string().lines().count()
+        );
+        assert!(!b.is_empty());
+    }
+}
@@ -25,19 +25,23 @@
 use derive_more::From;
 use serde::{
     de::{self, Deserializer},
-    Deserialize,
+    ser::Serializer,
+    Deserialize, Serialize,
 };
 
 use std::fmt;
 use std::str::FromStr;
 
 use crate::{
-    AppEvent, CheckRun, CheckSuite, Comment, DateTime, Installation, Issue,
-    Label, Oid, PullRequest, Repository, Review, ShortRepo, User,
+    AppEvent, AppId, CheckRun, CheckSuite, ClientPayload, Comment, DateTime,
+    DeployKey, HookId, Installation, InstallationId, Issue, IssueNumber, Label,
+    Milestone, Oid, Package, PullRequest, Repository, RequestedAction, Review,
+    ReviewComment, ReviewThread, Ruleset, ShortRepo, Team, User,
 };
 
 /// GitHub events that are specified in the X-Github-Event header.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 pub enum EventType {
     /// (Special event.) Any time any event is triggered (Wildcard Event).
     Wildcard,
@@ -67,6 +71,9 @@ pub enum EventType {
     /// Any time a Branch or Tag is deleted.
     Delete,
 
+    /// Any time a deploy key is added or removed from a repository.
+    DeployKey,
+
     /// Any time a Repository has a new deployment created from the API.
     Deployment,
 
@@ -120,6 +127,10 @@ pub enum EventType {
     /// only.
     Membership,
 
+    /// Triggered when the webhook that this event is configured on is
+    /// deleted.
+    Meta,
+
     /// Any time a Milestone is created, closed, opened, edited, or deleted.
     Milestone,
 
@@ -131,6 +142,9 @@ pub enum EventType {
     /// hooks only.
     OrgBlock,
 
+    /// Triggered when a package is published or updated in GitHub Packages.
+    Package,
+
     /// Any time a Pages site is built or results in a failed build.
     PageBuild,
 
@@ -161,6 +175,9 @@ pub enum EventType {
     /// Any time a pull request review is submitted, edited, or dismissed.
     PullRequestReview,
 
+    /// Any time a pull request review thread is resolved or unresolved.
+    PullRequestReviewThread,
+
     /// Any Git push to a Repository, including editing tags or branches.
     /// Commits via API actions that update references are also counted.
     /// This is the default event.
@@ -169,10 +186,18 @@ pub enum EventType {
     /// Any time a Release is published in a Repository.
     Release,
 
+    /// Triggered when a package is published or updated in GitHub Packages.
+    /// This is a legacy event superseded by `Package`.
+    RegistryPackage,
+
     /// Any time a Repository is created, deleted (organization hooks
     /// only), archived, unarchived, made public, or made private.
     Repository,
 
+    /// Triggered when a GitHub App sends a `POST` request to the "Create a
+    /// repository dispatch event" endpoint.
+    RepositoryDispatch,
+
     /// Triggered when a successful, cancelled, or failed repository import
     /// finishes for a GitHub organization or a personal repository. To receive
     /// this event for a personal repository, you must create an empty
@@ -180,6 +205,9 @@ pub enum EventType {
     /// either the GitHub Importer or the Source imports API.
     RepositoryImport,
 
+    /// Triggered when a repository ruleset is created, edited, or deleted.
+    RepositoryRuleset,
+
     /// Triggered when a security alert is created, dismissed, or resolved.
     RepositoryVulnerabilityAlert,
 
@@ -189,6 +217,9 @@ pub enum EventType {
     /// Advisory webhooks are available to GitHub Apps only.
     SecurityAdvisory,
 
+    /// Any time a User stars or unstars a Repository.
+    Star,
+
     /// Any time a Repository has a status update from the API.
     Status,
 
@@ -215,6 +246,7 @@ impl EventType {
             EventType::ContentReference => "content_reference",
             EventType::Create => "create",
             EventType::Delete => "delete",
+            EventType::DeployKey => "deploy_key",
             EventType::Deployment => "deployment",
             EventType::DeploymentStatus => "deployment_status",
             EventType::Fork => "fork",
@@ -232,9 +264,11 @@ impl EventType {
             EventType::MarketplacePurchase => "marketplace_purchase",
             EventType::Member => "member",
             EventType::Membership => "membership",
+            EventType::Meta => "meta",
             EventType::Milestone => "milestone",
             EventType::Organization => "organization",
             EventType::OrgBlock => "org_block",
+            EventType::Package => "package",
             EventType::PageBuild => "page_build",
             EventType::ProjectCard => "project_card",
             EventType::ProjectColumn => "project_column",
@@ -245,14 +279,19 @@ impl EventType {
             EventType::PullRequestReviewComment => {
                 "pull_request_review_comment"
             }
+            EventType::PullRequestReviewThread => "pull_request_review_thread",
             EventType::Push => "push",
             EventType::Release => "release",
+            EventType::RegistryPackage => "registry_package",
             EventType::Repository => "repository",
+            EventType::RepositoryDispatch => "repository_dispatch",
             EventType::RepositoryImport => "repository_import",
+            EventType::RepositoryRuleset => "repository_ruleset",
             EventType::RepositoryVulnerabilityAlert => {
                 "repository_vulnerability_alert"
             }
             EventType::SecurityAdvisory => "security_advisory",
+            EventType::Star => "star",
             EventType::Status => "status",
             EventType::Team => "team",
             EventType::TeamAdd => "team_add",
@@ -274,6 +313,7 @@ impl FromStr for EventType {
             "content_reference" => Ok(EventType::ContentReference),
             "create" => Ok(EventType::Create),
             "delete" => Ok(EventType::Delete),
+            "deploy_key" => Ok(EventType::DeployKey),
             "deployment" => Ok(EventType::Deployment),
             "deployment_status" => Ok(EventType::DeploymentStatus),
             "fork" => Ok(EventType::Fork),
@@ -295,9 +335,11 @@ impl FromStr for EventType {
             "marketplace_purchase" => Ok(EventType::MarketplacePurchase),
             "member" => Ok(EventType::Member),
             "membership" => Ok(EventType::Membership),
+            "meta" => Ok(EventType::Meta),
             "milestone" => Ok(EventType::Milestone),
             "organization" => Ok(EventType::Organization),
             "org_block" => Ok(EventType::OrgBlock),
+            "package" => Ok(EventType::Package),
             "page_build" => Ok(EventType::PageBuild),
             "project_card" => Ok(EventType::ProjectCard),
             "project_column" => Ok(EventType::ProjectColumn),
@@ -308,14 +350,21 @@ impl FromStr for EventType {
                 Ok(EventType::PullRequestReviewComment)
             }
             "pull_request_review" => Ok(EventType::PullRequestReview),
+            "pull_request_review_thread" => {
+                Ok(EventType::PullRequestReviewThread)
+            }
             "push" => Ok(EventType::Push),
             "release" => Ok(EventType::Release),
+            "registry_package" => Ok(EventType::RegistryPackage),
             "repository" => Ok(EventType::Repository),
+            "repository_dispatch" => Ok(EventType::RepositoryDispatch),
             "repository_import" => Ok(EventType::RepositoryImport),
+            "repository_ruleset" => Ok(EventType::RepositoryRuleset),
             "repository_vulnerability_alert" => {
                 Ok(EventType::RepositoryVulnerabilityAlert)
             }
             "security_advisory" => Ok(EventType::SecurityAdvisory),
+            "star" => Ok(EventType::Star),
             "status" => Ok(EventType::Status),
             "team" => Ok(EventType::Team),
             "team_add" => Ok(EventType::TeamAdd),
@@ -325,6 +374,15 @@ impl FromStr for EventType {
     }
 }
 
+impl Serialize for EventType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
 impl<'de> Deserialize<'de> for EventType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -346,8 +404,18 @@ impl fmt::Display for EventType {
 /// For documentation on each of these events, see:
 /// https://developer.github.com/v3/activity/events/types/
 #[derive(
-    Deserialize, From, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    From,
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[allow(clippy::large_enum_variant)]
 pub enum Event {
     Ping(PingEvent),
@@ -357,6 +425,7 @@ pub enum Event {
     // ContentReference(ContentReferenceEvent),
     Create(CreateEvent),
     Delete(DeleteEvent),
+    DeployKey(DeployKeyEvent),
     // Deployment(DeploymentEvent),
     // DeploymentStatus(DeploymentStatusEvent),
     // Fork(ForkEvent),
@@ -374,9 +443,11 @@ pub enum Event {
     // MarketplacePurchase(MarketplacePurchaseEvent),
     // Member(MemberEvent),
     // Membership(MembershipEvent),
+    Meta(MetaEvent),
     // Milestone(MilestoneEvent),
     // Organization(OrganizationEvent),
     // OrgBlock(OrgBlockEvent),
+    Package(PackageEvent),
     // PageBuild(PageBuildEvent),
     // ProjectCard(ProjectCardEvent),
     // ProjectColumn(ProjectColumnEvent),
@@ -385,20 +456,252 @@ pub enum Event {
     PullRequest(PullRequestEvent),
     PullRequestReview(PullRequestReviewEvent),
     PullRequestReviewComment(PullRequestReviewCommentEvent),
+    PullRequestReviewThread(PullRequestReviewThreadEvent),
     Push(PushEvent),
     // Release(ReleaseEvent),
+    RegistryPackage(RegistryPackageEvent),
     Repository(RepositoryEvent),
+    RepositoryDispatch(RepositoryDispatchEvent),
     // RepositoryImport(RepositoryImportEvent),
+    RepositoryRuleset(RepositoryRulesetEvent),
     // RepositoryVulnerabilityAlert(RepositoryVulnerabilityAlertEvent),
     // SecurityAdvisory(SecurityAdvisoryEvent),
+    Star(StarEvent),
     // Status(StatusEvent),
     // Team(TeamEvent),
     // TeamAdd(TeamAddEvent),
     Watch(WatchEvent),
 }
 
+impl Event {
+    /// Returns the type of the event, as sent in the `X-GitHub-Event` header.
+    pub fn event_type(&self) -> EventType {
+        match self {
+            Event::Ping(_) => EventType::Ping,
+            Event::CheckRun(_) => EventType::CheckRun,
+            Event::CheckSuite(_) => EventType::CheckSuite,
+            Event::CommitComment(_) => EventType::CommitComment,
+            Event::Create(_) => EventType::Create,
+            Event::Delete(_) => EventType::Delete,
+            Event::DeployKey(_) => EventType::DeployKey,
+            Event::GitHubAppAuthorization(_) => {
+                EventType::GitHubAppAuthorization
+            }
+            Event::Gollum(_) => EventType::Gollum,
+            Event::Installation(_) => EventType::Installation,
+            Event::InstallationRepositories(_) => {
+                EventType::InstallationRepositories
+            }
+            Event::IntegrationInstallation(_) => {
+                EventType::IntegrationInstallation
+            }
+            Event::IntegrationInstallationRepositories(_) => {
+                EventType::IntegrationInstallationRepositories
+            }
+            Event::IssueComment(_) => EventType::IssueComment,
+            Event::Issues(_) => EventType::Issues,
+            Event::Label(_) => EventType::Label,
+            Event::Meta(_) => EventType::Meta,
+            Event::Package(_) => EventType::Package,
+            Event::PullRequest(_) => EventType::PullRequest,
+            Event::PullRequestReview(_) => EventType::PullRequestReview,
+            Event::PullRequestReviewComment(_) => {
+                EventType::PullRequestReviewComment
+            }
+            Event::PullRequestReviewThread(_) => {
+                EventType::PullRequestReviewThread
+            }
+            Event::Push(_) => EventType::Push,
+            Event::RegistryPackage(_) => EventType::RegistryPackage,
+            Event::Repository(_) => EventType::Repository,
+            Event::RepositoryDispatch(_) => EventType::RepositoryDispatch,
+            Event::RepositoryRuleset(_) => EventType::RepositoryRuleset,
+            Event::Star(_) => EventType::Star,
+            Event::Watch(_) => EventType::Watch,
+        }
+    }
+
+    /// Serializes only the payload of the event, as GitHub sends it in the
+    /// body of a webhook.
+    ///
+    /// The `Serialize` implementation of `Event` includes the variant name so
+    /// that it can be deserialized again without knowing the event type.
+    /// Use this instead to forward a webhook along with its `event_type`.
+    pub fn serialize_payload<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Event::Ping(e) => e.serialize(serializer),
+            Event::CheckRun(e) => e.serialize(serializer),
+            Event::CheckSuite(e) => e.serialize(serializer),
+            Event::CommitComment(e) => e.serialize(serializer),
+            Event::Create(e) => e.serialize(serializer),
+            Event::Delete(e) => e.serialize(serializer),
+            Event::DeployKey(e) => e.serialize(serializer),
+            Event::GitHubAppAuthorization(e) => e.serialize(serializer),
+            Event::Gollum(e) => e.serialize(serializer),
+            Event::Installation(e) => e.serialize(serializer),
+            Event::InstallationRepositories(e) => e.serialize(serializer),
+            Event::IntegrationInstallation(e) => e.serialize(serializer),
+            Event::IntegrationInstallationRepositories(e) => {
+                e.serialize(serializer)
+            }
+            Event::IssueComment(e) => e.serialize(serializer),
+            Event::Issues(e) => e.serialize(serializer),
+            Event::Label(e) => e.serialize(serializer),
+            Event::Meta(e) => e.serialize(serializer),
+            Event::Package(e) => e.serialize(serializer),
+            Event::PullRequest(e) => e.serialize(serializer),
+            Event::PullRequestReview(e) => e.serialize(serializer),
+            Event::PullRequestReviewComment(e) => e.serialize(serializer),
+            Event::PullRequestReviewThread(e) => e.serialize(serializer),
+            Event::Push(e) => e.serialize(serializer),
+            Event::RegistryPackage(e) => e.serialize(serializer),
+            Event::Repository(e) => e.serialize(serializer),
+            Event::RepositoryDispatch(e) => e.serialize(serializer),
+            Event::RepositoryRuleset(e) => e.serialize(serializer),
+            Event::Star(e) => e.serialize(serializer),
+            Event::Watch(e) => e.serialize(serializer),
+        }
+    }
+
+    /// Deserializes the payload of a webhook, given the event type from its
+    /// `X-GitHub-Event` header.
+    ///
+    /// This is the inverse of [`Event::serialize_payload`]. An error is
+    /// returned if the event type is not supported.
+    pub fn deserialize_payload<'de, D>(
+        event_type: EventType,
+        deserializer: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match event_type {
+            EventType::Ping => {
+                PingEvent::deserialize(deserializer).map(Event::Ping)
+            }
+            EventType::CheckRun => {
+                CheckRunEvent::deserialize(deserializer).map(Event::CheckRun)
+            }
+            EventType::CheckSuite => CheckSuiteEvent::deserialize(deserializer)
+                .map(Event::CheckSuite),
+            EventType::CommitComment => {
+                CommitCommentEvent::deserialize(deserializer)
+                    .map(Event::CommitComment)
+            }
+            EventType::Create => {
+                CreateEvent::deserialize(deserializer).map(Event::Create)
+            }
+            EventType::Delete => {
+                DeleteEvent::deserialize(deserializer).map(Event::Delete)
+            }
+            EventType::DeployKey => {
+                DeployKeyEvent::deserialize(deserializer).map(Event::DeployKey)
+            }
+            EventType::GitHubAppAuthorization => {
+                GitHubAppAuthorizationEvent::deserialize(deserializer)
+                    .map(Event::GitHubAppAuthorization)
+            }
+            EventType::Gollum => {
+                GollumEvent::deserialize(deserializer).map(Event::Gollum)
+            }
+            EventType::Installation => {
+                InstallationEvent::deserialize(deserializer)
+                    .map(Event::Installation)
+            }
+            EventType::InstallationRepositories => {
+                InstallationRepositoriesEvent::deserialize(deserializer)
+                    .map(Event::InstallationRepositories)
+            }
+            EventType::IntegrationInstallation => {
+                IntegrationInstallationEvent::deserialize(deserializer)
+                    .map(Event::IntegrationInstallation)
+            }
+            EventType::IntegrationInstallationRepositories => {
+                IntegrationInstallationRepositoriesEvent::deserialize(
+                    deserializer,
+                )
+                .map(Event::IntegrationInstallationRepositories)
+            }
+            EventType::IssueComment => {
+                IssueCommentEvent::deserialize(deserializer)
+                    .map(Event::IssueComment)
+            }
+            EventType::Issues => {
+                IssuesEvent::deserialize(deserializer).map(Event::Issues)
+            }
+            EventType::Label => {
+                LabelEvent::deserialize(deserializer).map(Event::Label)
+            }
+            EventType::Meta => {
+                MetaEvent::deserialize(deserializer).map(Event::Meta)
+            }
+            EventType::Package => {
+                PackageEvent::deserialize(deserializer).map(Event::Package)
+            }
+            EventType::PullRequest => {
+                PullRequestEvent::deserialize(deserializer)
+                    .map(Event::PullRequest)
+            }
+            EventType::PullRequestReview => {
+                PullRequestReviewEvent::deserialize(deserializer)
+                    .map(Event::PullRequestReview)
+            }
+            EventType::PullRequestReviewComment => {
+                PullRequestReviewCommentEvent::deserialize(deserializer)
+                    .map(Event::PullRequestReviewComment)
+            }
+            EventType::PullRequestReviewThread => {
+                PullRequestReviewThreadEvent::deserialize(deserializer)
+                    .map(Event::PullRequestReviewThread)
+            }
+            EventType::Push => {
+                PushEvent::deserialize(deserializer).map(Event::Push)
+            }
+            EventType::RegistryPackage => {
+                RegistryPackageEvent::deserialize(deserializer)
+                    .map(Event::RegistryPackage)
+            }
+            EventType::Repository => RepositoryEvent::deserialize(deserializer)
+                .map(Event::Repository),
+            EventType::RepositoryDispatch => {
+                RepositoryDispatchEvent::deserialize(deserializer)
+                    .map(Event::RepositoryDispatch)
+            }
+            EventType::RepositoryRuleset => {
+                RepositoryRulesetEvent::deserialize(deserializer)
+                    .map(Event::RepositoryRuleset)
+            }
+            EventType::Star => {
+                StarEvent::deserialize(deserializer).map(Event::Star)
+            }
+            EventType::Watch => {
+                WatchEvent::deserialize(deserializer).map(Event::Watch)
+            }
+            _ => Err(de::Error::custom(format!(
+                "unsupported event type `{}`",
+                event_type
+            ))),
+        }
+    }
+
+    /// Parses the JSON payload of a webhook, given the event type from its
+    /// `X-GitHub-Event` header.
+    pub fn from_json(
+        event_type: EventType,
+        json: &str,
+    ) -> serde_json::Result<Self> {
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let event = Self::deserialize_payload(event_type, &mut deserializer)?;
+        deserializer.end()?;
+        Ok(event)
+    }
+}
+
 impl AppEvent for Event {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         match self {
             Event::Ping(e) => e.installation(),
             Event::CheckRun(e) => e.installation(),
@@ -406,6 +709,7 @@ impl AppEvent for Event {
             Event::CommitComment(e) => e.installation(),
             Event::Create(e) => e.installation(),
             Event::Delete(e) => e.installation(),
+            Event::DeployKey(e) => e.installation(),
             Event::GitHubAppAuthorization(e) => e.installation(),
             Event::Gollum(e) => e.installation(),
             Event::Installation(e) => e.installation(),
@@ -415,70 +719,175 @@ impl AppEvent for Event {
             Event::IssueComment(e) => e.installation(),
             Event::Issues(e) => e.installation(),
             Event::Label(e) => e.installation(),
+            Event::Meta(e) => e.installation(),
+            Event::Package(e) => e.installation(),
             Event::PullRequest(e) => e.installation(),
             Event::PullRequestReview(e) => e.installation(),
             Event::PullRequestReviewComment(e) => e.installation(),
+            Event::PullRequestReviewThread(e) => e.installation(),
             Event::Push(e) => e.installation(),
+            Event::RegistryPackage(e) => e.installation(),
             Event::Repository(e) => e.installation(),
+            Event::RepositoryDispatch(e) => e.installation(),
+            Event::RepositoryRuleset(e) => e.installation(),
+            Event::Star(e) => e.installation(),
             Event::Watch(e) => e.installation(),
         }
     }
 }
 
-/// The App installation ID.
+/// Short info about an app installation.
+///
+/// This used to be called `InstallationId`. That name now refers to the ID
+/// itself.
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
-pub struct InstallationId {
-    pub id: u64,
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ShortInstallation {
+    pub id: InstallationId,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum Hook {
     Repository(RepoHook),
+    Organization(OrgHook),
     App(AppHook),
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RepoHook {
-    pub id: u64,
+    pub id: HookId,
     pub name: String,
     pub active: bool,
     pub events: Vec<EventType>,
     pub config: HookConfig,
     pub updated_at: DateTime,
     pub created_at: DateTime,
-    pub url: String,
-    pub test_url: String,
-    pub ping_url: String,
+
+    /// The API URL of the hook. This is not included in `MetaEvent`s, which
+    /// describe a hook that was already deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// The URL used to test the hook. This is not included in `MetaEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_url: Option<String>,
+
+    /// The URL used to ping the hook. This is not included in `MetaEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ping_url: Option<String>,
+
+    /// The response to the last delivery. This is only included in
+    /// `PingEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_response: Option<HookResponse>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HookConfig {
     pub content_type: String,
     pub insecure_ssl: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub secret: Option<String>,
     pub url: String,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// A webhook installed on an organization.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OrgHook {
+    pub id: HookId,
+    pub name: String,
+    pub active: bool,
+    pub events: Vec<EventType>,
+    pub config: HookConfig,
+    pub updated_at: DateTime,
+    pub created_at: DateTime,
+
+    /// The API URL of the hook. This is not included in `MetaEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// The URL used to ping the hook. This is not included in `MetaEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ping_url: Option<String>,
+
+    /// The response to the last delivery. This is only included in
+    /// `PingEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_response: Option<HookResponse>,
+}
+
+/// The response to the last delivery of a hook.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct HookResponse {
+    pub code: Option<u16>,
+    pub status: String,
+    pub message: Option<String>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AppHook {
-    pub id: u64,
+    pub id: HookId,
     pub name: String,
     pub active: bool,
     pub events: Vec<EventType>,
     pub config: HookConfig,
     pub updated_at: DateTime,
     pub created_at: DateTime,
-    pub app_id: u64,
+    pub app_id: AppId,
+
+    /// The response to the last delivery. This is only included in
+    /// `PingEvent`s.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_response: Option<HookResponse>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PingEvent {
     pub zen: String,
-    pub hook_id: u64,
+    pub hook_id: HookId,
     pub hook: Hook,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub repository: Option<Repository>,
     pub sender: Option<User>,
 }
@@ -486,8 +895,19 @@ pub struct PingEvent {
 impl AppEvent for PingEvent {}
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CheckRunEventAction {
     /// A new check run was created.
@@ -505,13 +925,22 @@ pub enum CheckRunEventAction {
 }
 
 /// See: https://developer.github.com/v3/activity/events/types/#checkrunevent
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct CheckRunEvent {
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CheckRunEvent<I = String> {
     /// The action performed.
     pub action: CheckRunEventAction,
 
     /// The check run.
-    pub check_run: CheckRun,
+    pub check_run: CheckRun<I>,
+
+    /// The action requested by the user. This is only present if `action` is
+    /// `RequestedAction`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_action: Option<RequestedAction<I>>,
 
     /// The repository associated with this event.
     pub repository: Repository,
@@ -520,18 +949,29 @@ pub struct CheckRunEvent {
     pub sender: User,
 
     /// The App installation ID.
-    pub installation: InstallationId,
+    pub installation: ShortInstallation,
 }
 
-impl AppEvent for CheckRunEvent {
-    fn installation(&self) -> Option<u64> {
+impl<I> AppEvent for CheckRunEvent<I> {
+    fn installation(&self) -> Option<InstallationId> {
         Some(self.installation.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CheckSuiteEventAction {
     Completed,
@@ -543,25 +983,26 @@ impl CheckSuiteEventAction {
     /// Returns `true` if the action indicates that the check suite is
     /// completed.
     pub fn is_completed(self) -> bool {
-        match self {
-            CheckSuiteEventAction::Completed => false,
-            _ => false,
-        }
+        self == CheckSuiteEventAction::Completed
     }
 
     /// Returns `true` if the action indicates that the check suite has been
     /// requested or re-requested.
     pub fn is_requested(self) -> bool {
-        match self {
+        matches!(
+            self,
             CheckSuiteEventAction::Requested
-            | CheckSuiteEventAction::Rerequested => true,
-            _ => false,
-        }
+                | CheckSuiteEventAction::Rerequested
+        )
     }
 }
 
 /// See: https://developer.github.com/v3/activity/events/types/#checkrunevent
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CheckSuiteEvent {
     /// The action performed.
     pub action: CheckSuiteEventAction,
@@ -576,7 +1017,7 @@ pub struct CheckSuiteEvent {
     pub sender: User,
 
     /// The App installation ID.
-    pub installation: InstallationId,
+    pub installation: ShortInstallation,
 }
 
 impl CheckSuiteEvent {
@@ -587,20 +1028,35 @@ impl CheckSuiteEvent {
 }
 
 impl AppEvent for CheckSuiteEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         Some(self.installation.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CommitCommentAction {
     Created,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CommitCommentEvent {
     pub action: CommitCommentAction,
 
@@ -614,18 +1070,30 @@ pub struct CommitCommentEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for CommitCommentEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum CreateRefType {
     Repository,
@@ -633,7 +1101,11 @@ pub enum CreateRefType {
     Tag,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CreateEvent {
     /// The Git ref type.
     pub ref_type: CreateRefType,
@@ -657,25 +1129,41 @@ pub struct CreateEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for CreateEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum DeleteRefType {
     Branch,
     Tag,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct DeleteEvent {
     /// The Git ref type.
     pub ref_type: DeleteRefType,
@@ -691,18 +1179,79 @@ pub struct DeleteEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for DeleteEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DeployKeyAction {
+    Created,
+    Deleted,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeployKeyEvent {
+    /// The action that was performed.
+    pub action: DeployKeyAction,
+
+    /// The deploy key that was added or removed.
+    pub key: DeployKey,
+
+    /// The repository associated with this event.
+    pub repository: Repository,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for DeployKeyEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum GitHubAppAuthorizationAction {
     Revoked,
@@ -710,7 +1259,11 @@ pub enum GitHubAppAuthorizationAction {
 
 /// Triggered when someone revokes their authorization of a GitHub App. A GitHub
 /// App receives this webhook by default and cannot unsubscribe from this event.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GitHubAppAuthorizationEvent {
     pub action: GitHubAppAuthorizationAction,
 
@@ -718,25 +1271,40 @@ pub struct GitHubAppAuthorizationEvent {
     pub sender: User,
 
     /// The App installation ID.
-    pub installation: InstallationId,
+    pub installation: ShortInstallation,
 }
 
 impl AppEvent for GitHubAppAuthorizationEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         Some(self.installation.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PageAction {
     Created,
     Edited,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PageEvent {
     pub page_name: String,
     pub title: String,
@@ -746,7 +1314,11 @@ pub struct PageEvent {
     pub html_url: String,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct GollumEvent {
     /// The pages that were created or edited.
     pub pages: Vec<PageEvent>,
@@ -758,18 +1330,30 @@ pub struct GollumEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for GollumEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum InstallationAction {
     Created,
@@ -777,7 +1361,11 @@ pub enum InstallationAction {
     NewPermissionsAccepted,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InstallationEvent {
     pub action: InstallationAction,
     pub installation: Installation,
@@ -785,21 +1373,36 @@ pub struct InstallationEvent {
 }
 
 impl AppEvent for InstallationEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         Some(self.installation.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum InstallationRepositoriesAction {
     Added,
     Removed,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct InstallationRepositoriesEvent {
     pub action: InstallationRepositoriesAction,
     pub installation: Installation,
@@ -810,34 +1413,53 @@ pub struct InstallationRepositoriesEvent {
 }
 
 impl AppEvent for InstallationRepositoriesEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         Some(self.installation.id)
     }
 }
 
 /// Event deprecated by GitHub. Use `InstallationEvent` instead.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IntegrationInstallationEvent {}
 
 impl AppEvent for IntegrationInstallationEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         None
     }
 }
 
 /// Event deprecated by GitHub. Use `InstallationRepositoriesEvent` instead.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IntegrationInstallationRepositoriesEvent {}
 
 impl AppEvent for IntegrationInstallationRepositoriesEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         None
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum IssueCommentAction {
     Created,
@@ -845,7 +1467,11 @@ pub enum IssueCommentAction {
     Deleted,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IssueCommentEvent {
     /// The action that was performed.
     pub action: IssueCommentAction,
@@ -863,18 +1489,30 @@ pub struct IssueCommentEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for IssueCommentEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum IssueAction {
     Opened,
@@ -893,21 +1531,35 @@ pub enum IssueAction {
     Demilestoned,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ChangeFrom {
     pub from: String,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IssueChanges {
     /// A change to the body, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<ChangeFrom>,
 
     /// A change to the title, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<ChangeFrom>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IssuesEvent {
     /// The action that was performed.
     pub action: IssueAction,
@@ -916,14 +1568,17 @@ pub struct IssuesEvent {
     pub issue: Issue,
 
     /// Changes to the issues (if the action is `Edited`).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changes: Option<IssueChanges>,
 
     /// The label that was added or removed (if the action is `Labeled` or
     /// `Unlabeled`).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<Label>,
 
     /// The optional user who was assigned or unassigned from the issue (if the
     /// action is `Assigned` or `Unassigned`).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub assignee: Option<User>,
 
     /// The repository associated with this event.
@@ -933,18 +1588,30 @@ pub struct IssuesEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for IssuesEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum LabelAction {
     Created,
@@ -952,16 +1619,26 @@ pub enum LabelAction {
     Deleted,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LabelChanges {
     /// A change to the body, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<ChangeFrom>,
 
     /// A change to the title, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<ChangeFrom>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct LabelEvent {
     /// The action that was performed.
     pub action: LabelAction,
@@ -970,6 +1647,7 @@ pub struct LabelEvent {
     pub label: Label,
 
     /// Changes to the issues (if the action is `Edited`).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changes: Option<LabelChanges>,
 
     /// The repository associated with this event.
@@ -979,54 +1657,256 @@ pub struct LabelEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for LabelEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MetaAction {
+    Deleted,
+}
+
+/// Sent when the webhook this event is configured on is deleted. This is the
+/// last event the webhook will receive.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MetaEvent {
+    /// The action that was performed.
+    pub action: MetaAction,
+
+    /// The ID of the deleted webhook.
+    pub hook_id: HookId,
+
+    /// The deleted webhook.
+    pub hook: Hook,
+
+    /// The repository associated with this event. This is `None` for
+    /// organization and app hooks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<Repository>,
+
+    /// The user who triggered the event.
+    pub sender: Option<User>,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for MetaEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PackageAction {
+    Published,
+    Updated,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackageEvent {
+    /// The action that was performed.
+    pub action: PackageAction,
+
+    /// The package that was published or updated, including the affected
+    /// version.
+    pub package: Package,
+
+    /// The repository associated with this event, if any.
+    pub repository: Option<Repository>,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for PackageEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PullRequestAction {
     Assigned,
     Unassigned,
+    AutoMergeEnabled,
+    AutoMergeDisabled,
     ReviewRequested,
     ReviewRequestRemoved,
     Labeled,
     Unlabeled,
+    Milestoned,
+    Demilestoned,
     Opened,
     Edited,
     Closed,
+    ConvertedToDraft,
     ReadyForReview,
     Locked,
     Unlocked,
     Reopened,
     Synchronize,
+    Enqueued,
+    Dequeued,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestBaseChanges {
+    /// The previous name of the base branch.
+    #[serde(rename = "ref")]
+    pub git_ref: ChangeFrom,
+
+    /// The previous SHA of the base branch.
+    pub sha: ChangeFrom,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestChanges {
+    /// A change to the body, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<ChangeFrom>,
+
+    /// A change to the title, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<ChangeFrom>,
+
+    /// A change to the base branch, if the pull request was retargeted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<PullRequestBaseChanges>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequestEvent {
-    /// The action that was performed. Can be one of "assigned", "unassigned",
-    /// "review_requested", "review_request_removed", "labeled", "unlabeled",
-    /// "opened", "edited", "closed", or "reopened". If the action is "closed"
-    /// and the `merged` key is `false`, the pull request was closed with
-    /// unmerged commits. If the action is "closed" and the `merged` key is
-    /// `true`, the pull request was merged. While webhooks are also triggered
-    /// when a pull request is synchronized, Events API timelines don't include
-    /// pull request events with the "synchronize" action.
+    /// The action that was performed. If the action is "closed" and the
+    /// `merged` key is `false`, the pull request was closed with unmerged
+    /// commits. If the action is "closed" and the `merged` key is `true`, the
+    /// pull request was merged. While webhooks are also triggered when a pull
+    /// request is synchronized, Events API timelines don't include pull
+    /// request events with the "synchronize" action.
     pub action: PullRequestAction,
 
     /// The pull request number.
-    pub number: u64,
+    pub number: IssueNumber,
 
     /// The pull request itself.
     pub pull_request: PullRequest,
 
+    /// Changes to the pull request (if the action is `Edited`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<PullRequestChanges>,
+
+    /// The user whose review was requested or removed (if the action is
+    /// `ReviewRequested` or `ReviewRequestRemoved` and a user was requested).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_reviewer: Option<User>,
+
+    /// The team whose review was requested or removed (if the action is
+    /// `ReviewRequested` or `ReviewRequestRemoved` and a team was requested).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested_team: Option<Team>,
+
+    /// The label that was added or removed (if the action is `Labeled` or
+    /// `Unlabeled`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<Label>,
+
+    /// The user who was assigned or unassigned (if the action is `Assigned`
+    /// or `Unassigned`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<User>,
+
+    /// The milestone that was added or removed (if the action is `Milestoned`
+    /// or `Demilestoned`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Milestone>,
+
+    /// The reason auto-merge was disabled or the pull request was removed
+    /// from the merge queue (if the action is `AutoMergeDisabled` or
+    /// `Dequeued`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// The previous head commit (if the action is `Synchronize`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Oid>,
+
+    /// The new head commit (if the action is `Synchronize`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Oid>,
+
     /// The repository associated with this event.
     pub repository: Repository,
 
@@ -1034,18 +1914,30 @@ pub struct PullRequestEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for PullRequestEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PullRequestReviewAction {
     Submitted,
@@ -1053,12 +1945,21 @@ pub enum PullRequestReviewAction {
     Dismissed,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequestReviewChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<ChangeFrom>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequestReviewEvent {
     /// The action that was performed.
     pub action: PullRequestReviewAction,
@@ -1067,6 +1968,7 @@ pub struct PullRequestReviewEvent {
     pub review: Review,
 
     /// Changes to the review if the action is `Edited`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changes: Option<PullRequestReviewChanges>,
 
     /// The pull request itself.
@@ -1079,18 +1981,30 @@ pub struct PullRequestReviewEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for PullRequestReviewEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PullRequestReviewCommentAction {
     Created,
@@ -1098,17 +2012,27 @@ pub enum PullRequestReviewCommentAction {
     Deleted,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequestReviewCommentChanges {
     /// A change to the body, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<ChangeFrom>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequestReviewCommentEvent {
     pub action: PullRequestReviewCommentAction,
 
     /// The changes to the comment if the action was `Edited`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub changes: Option<PullRequestReviewCommentChanges>,
 
     /// The pull request itself.
@@ -1118,35 +2042,101 @@ pub struct PullRequestReviewCommentEvent {
     pub repository: Repository,
 
     /// The comment in question.
-    pub comment: Comment,
+    pub comment: ReviewComment,
 
     /// The user who triggered the event.
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for PullRequestReviewCommentEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestReviewThreadAction {
+    Resolved,
+    Unresolved,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestReviewThreadEvent {
+    /// The action that was performed.
+    pub action: PullRequestReviewThreadAction,
+
+    /// The thread that was resolved or unresolved.
+    pub thread: ReviewThread,
+
+    /// The pull request itself.
+    pub pull_request: PullRequest,
+
+    /// The repository associated with this event.
+    pub repository: Repository,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for PullRequestReviewThreadEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Pusher {
     pub name: String,
     pub email: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PushAuthor {
     pub name: String,
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PushCommit {
     pub id: Oid,
     pub tree_id: Oid,
@@ -1161,7 +2151,11 @@ pub struct PushCommit {
     pub modified: Vec<String>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PushEvent {
     /// The Git ref string that was pushed.
     #[serde(rename = "ref")]
@@ -1204,18 +2198,61 @@ pub struct PushEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for PushEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
+/// Event deprecated by GitHub. Use `PackageEvent` instead.
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RegistryPackageEvent {
+    /// The action that was performed.
+    pub action: PackageAction,
+
+    /// The package that was published or updated, including the affected
+    /// version.
+    pub registry_package: Package,
+
+    /// The repository associated with this event, if any.
+    pub repository: Option<Repository>,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for RegistryPackageEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum RepositoryAction {
     Created,
@@ -1226,7 +2263,11 @@ pub enum RepositoryAction {
     Privatized,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct RepositoryEvent {
     /// The action that was performed.
     pub action: RepositoryAction,
@@ -1238,24 +2279,200 @@ pub struct RepositoryEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for RepositoryEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
 
+/// Triggered by a `RepositoryDispatch` request.
+///
+/// The `client_payload` is whatever was sent in the request. By default, it is
+/// left as arbitrary JSON (see `ClientPayload`), but it can be decoded into a
+/// concrete type by specifying `P`.
+///
+/// `P` defaults to `ClientPayload` rather than `serde_json::Value` so that
+/// `Event` can still implement `Ord` and `Hash`. Use
+/// `RepositoryDispatchEvent<serde_json::Value>` to get a plain `Value`.
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
 )]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RepositoryDispatchEvent<P = ClientPayload> {
+    /// The `event_type` that was specified in the request.
+    pub action: String,
+
+    /// The name of the repository's default branch.
+    pub branch: String,
+
+    /// The `client_payload` that was specified in the request.
+    pub client_payload: P,
+
+    /// The repository associated with this event.
+    pub repository: Repository,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl<P> AppEvent for RepositoryDispatchEvent<P> {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RepositoryRulesetAction {
+    Created,
+    Edited,
+    Deleted,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RepositoryRulesetChanges {
+    /// A change to the name, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<ChangeFrom>,
+
+    /// A change to the enforcement, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforcement: Option<ChangeFrom>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RepositoryRulesetEvent {
+    /// The action that was performed.
+    pub action: RepositoryRulesetAction,
+
+    /// The ruleset that was affected.
+    pub repository_ruleset: Ruleset,
+
+    /// Changes to the ruleset (if the action is `Edited`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<RepositoryRulesetChanges>,
+
+    /// The repository associated with this event. This is `None` for
+    /// organization rulesets.
+    pub repository: Option<Repository>,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for RepositoryRulesetEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum StarAction {
+    Created,
+    Deleted,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StarEvent {
+    /// The action that was performed.
+    pub action: StarAction,
+
+    /// The time the star was created. This is `None` if the action is
+    /// `Deleted`.
+    pub starred_at: Option<DateTime>,
+
+    /// The repository associated with this event.
+    pub repository: Repository,
+
+    /// The user who triggered the event.
+    pub sender: User,
+
+    /// The App installation ID. This is only present for GitHub App events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
+}
+
+impl AppEvent for StarEvent {
+    fn installation(&self) -> Option<InstallationId> {
+        self.installation.map(|i| i.id)
+    }
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum WatchAction {
     Started,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WatchEvent {
     /// The action that was performed.
     pub action: WatchAction,
@@ -1267,11 +2484,60 @@ pub struct WatchEvent {
     pub sender: User,
 
     /// The App installation ID. This is only present for GitHub App events.
-    pub installation: Option<InstallationId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation: Option<ShortInstallation>,
 }
 
 impl AppEvent for WatchEvent {
-    fn installation(&self) -> Option<u64> {
+    fn installation(&self) -> Option<InstallationId> {
         self.installation.map(|i| i.id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn organization_hook() {
+        let event: PingEvent = serde_json::from_str(
+            r#"{
+                "zen": "Design for failure.",
+                "hook_id": 1,
+                "hook": {
+                    "type": "Organization",
+                    "id": 1,
+                    "name": "web",
+                    "active": true,
+                    "events": ["push", "pull_request"],
+                    "config": {
+                        "content_type": "json",
+                        "insecure_ssl": "0",
+                        "url": "https://example.com/webhook"
+                    },
+                    "updated_at": "2019-05-15T15:20:49Z",
+                    "created_at": "2019-05-15T15:20:49Z",
+                    "url": "https://api.github.com/orgs/octo/hooks/1",
+                    "ping_url": "https://api.github.com/orgs/octo/hooks/1/pings"
+                },
+                "sender": null
+            }"#,
+        )
+        .unwrap();
+
+        match event.hook {
+            Hook::Organization(hook) => {
+                assert_eq!(hook.id, HookId(1));
+                assert_eq!(
+                    hook.events,
+                    [EventType::Push, EventType::PullRequest]
+                );
+                assert_eq!(
+                    hook.ping_url.as_deref(),
+                    Some("https://api.github.com/orgs/octo/hooks/1/pings")
+                );
+            }
+            hook => panic!("expected an organization hook, got {:?}", hook),
+        }
+    }
+}
@@ -0,0 +1,753 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Factories and builders for constructing realistic payloads in tests.
+//!
+//! Everything here is deterministic: IDs, SHAs and URLs are derived from the
+//! names given, so the same inputs always produce the same values. URLs are
+//! derived consistently from the base URLs of github.com.
+//!
+//! This module requires the `test-util` feature.
+
+use chrono::TimeZone;
+
+use crate::{
+    DateTime, InstallationId, Issue, IssueId, IssueNumber, Label, Oid,
+    PullRequest, PullRequestAction, PullRequestEvent, PullRequestId,
+    PushAuthor, PushCommit, PushEvent, Pusher, RepoId, Repository, ShortCommit,
+    ShortInstallation, User, UserId,
+};
+
+const API_URL: &str = "https://api.github.com";
+const HTML_URL: &str = "https://github.com";
+
+/// Hashes a seed with FNV-1a.
+fn hash(seed: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in seed.bytes() {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Returns a stable, positive ID derived from the given seed.
+fn fake_id(seed: &str) -> u64 {
+    // The result is kept well below 2^53 so that it survives a round trip
+    // through JavaScript.
+    hash(seed) % 1_000_000_000 + 1
+}
+
+/// Returns a stable SHA derived from the given seed.
+fn fake_oid(seed: &str) -> Oid {
+    let mut bytes = Vec::with_capacity(20);
+    let mut i = 0;
+
+    while bytes.len() < 20 {
+        bytes
+            .extend_from_slice(&hash(&format!("{}:{}", seed, i)).to_le_bytes());
+        i += 1;
+    }
+
+    bytes.truncate(20);
+    Oid::from_hex(&hex::encode(bytes)).unwrap()
+}
+
+/// The time used for all timestamps.
+fn fake_time() -> DateTime {
+    DateTime::from(chrono::Utc.timestamp_opt(1_557_933_633, 0).unwrap())
+}
+
+impl User {
+    /// Creates a user with the given login.
+    pub fn fake(login: &str) -> Self {
+        let url = format!("{}/users/{}", API_URL, login);
+        let id = UserId(fake_id(login));
+
+        User {
+            login: login.to_string(),
+            id,
+            avatar_url: format!(
+                "https://avatars.githubusercontent.com/u/{}?v=4",
+                id
+            ),
+            gravatar_id: String::new(),
+            html_url: format!("{}/{}", HTML_URL, login),
+            followers_url: format!("{}/followers", url),
+            following_url: format!("{}/following{{/other_user}}", url),
+            gists_url: format!("{}/gists{{/gist_id}}", url),
+            starred_url: format!("{}/starred{{/owner}}{{/repo}}", url),
+            subscriptions_url: format!("{}/subscriptions", url),
+            organizations_url: format!("{}/orgs", url),
+            repos_url: format!("{}/repos", url),
+            events_url: format!("{}/events{{/privacy}}", url),
+            received_events_url: format!("{}/received_events", url),
+            site_admin: false,
+            url,
+        }
+    }
+}
+
+impl Repository {
+    /// Creates a public repository with the given full name (e.g.,
+    /// `octocat/hello-world`). The owner is created with `User::fake`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `full_name` is not of the form `owner/name`.
+    pub fn fake(full_name: &str) -> Self {
+        let (owner, name) = match full_name.find('/') {
+            Some(i) => (&full_name[..i], &full_name[i + 1..]),
+            None => panic!("invalid repository name {:?}", full_name),
+        };
+
+        let url = format!("{}/repos/{}", API_URL, full_name);
+        let html_url = format!("{}/{}", HTML_URL, full_name);
+
+        Repository {
+            id: RepoId(fake_id(full_name)),
+            owner: User::fake(owner),
+            name: name.to_string(),
+            full_name: full_name.to_string(),
+            description: None,
+            private: false,
+            fork: false,
+            archive_url: format!("{}/{{archive_format}}{{/ref}}", url),
+            assignees_url: format!("{}/assignees{{/user}}", url),
+            blobs_url: format!("{}/git/blobs{{/sha}}", url),
+            branches_url: format!("{}/branches{{/branch}}", url),
+            clone_url: format!("{}.git", html_url),
+            collaborators_url: format!(
+                "{}/collaborators{{/collaborator}}",
+                url
+            ),
+            comments_url: format!("{}/comments{{/number}}", url),
+            commits_url: format!("{}/commits{{/sha}}", url),
+            compare_url: format!("{}/compare/{{base}}...{{head}}", url),
+            contents_url: format!("{}/contents/{{+path}}", url),
+            contributors_url: format!("{}/contributors", url),
+            deployments_url: format!("{}/deployments", url),
+            downloads_url: format!("{}/downloads", url),
+            events_url: format!("{}/events", url),
+            forks_url: format!("{}/forks", url),
+            git_commits_url: format!("{}/git/commits{{/sha}}", url),
+            git_refs_url: format!("{}/git/refs{{/sha}}", url),
+            git_tags_url: format!("{}/git/tags{{/sha}}", url),
+            git_url: format!("git://github.com/{}.git", full_name),
+            hooks_url: format!("{}/hooks", url),
+            issue_comment_url: format!("{}/issues/comments{{/number}}", url),
+            issue_events_url: format!("{}/issues/events{{/number}}", url),
+            issues_url: format!("{}/issues{{/number}}", url),
+            keys_url: format!("{}/keys{{/key_id}}", url),
+            labels_url: format!("{}/labels{{/name}}", url),
+            languages_url: format!("{}/languages", url),
+            merges_url: format!("{}/merges", url),
+            milestones_url: format!("{}/milestones{{/number}}", url),
+            mirror_url: None,
+            notifications_url: format!(
+                "{}/notifications{{?since,all,participating}}",
+                url
+            ),
+            pulls_url: format!("{}/pulls{{/number}}", url),
+            releases_url: format!("{}/releases{{/id}}", url),
+            ssh_url: format!("git@github.com:{}.git", full_name),
+            stargazers_url: format!("{}/stargazers", url),
+            statuses_url: format!("{}/statuses/{{sha}}", url),
+            subscribers_url: format!("{}/subscribers", url),
+            subscription_url: format!("{}/subscription", url),
+            svn_url: html_url.clone(),
+            tags_url: format!("{}/tags", url),
+            teams_url: format!("{}/teams", url),
+            trees_url: format!("{}/git/trees{{/sha}}", url),
+            homepage: None,
+            language: None,
+            forks_count: 0,
+            stargazers_count: 0,
+            watchers_count: 0,
+            size: 0,
+            default_branch: "master".to_string(),
+            open_issues_count: 0,
+            has_issues: true,
+            has_wiki: true,
+            has_pages: false,
+            has_downloads: true,
+            archived: false,
+            pushed_at: fake_time(),
+            created_at: fake_time(),
+            updated_at: fake_time(),
+            url,
+            html_url,
+        }
+    }
+}
+
+impl Label {
+    /// Creates a label with the given name in a repository.
+    pub fn fake(repo: &Repository, name: &str) -> Self {
+        Label {
+            url: format!(
+                "{}/repos/{}/labels/{}",
+                API_URL, repo.full_name, name
+            ),
+            name: name.to_string(),
+            color: format!("{:06x}", fake_id(name) & 0xff_ffff),
+        }
+    }
+}
+
+impl ShortCommit {
+    /// Creates a reference to the head of a branch in a repository.
+    pub fn fake(repo: &Repository, branch: &str) -> Self {
+        ShortCommit {
+            label: format!("{}:{}", repo.owner.login, branch),
+            git_ref: branch.to_string(),
+            sha: fake_oid(&format!("{}:{}", repo.full_name, branch)),
+            user: repo.owner.clone(),
+        }
+    }
+}
+
+impl PullRequest {
+    /// Creates an open pull request in a repository. The pull request is
+    /// opened by the owner of the repository and merges the branch
+    /// `feature-<number>` into the default branch.
+    pub fn fake(repo: &Repository, number: IssueNumber) -> Self {
+        let url =
+            format!("{}/repos/{}/pulls/{}", API_URL, repo.full_name, number);
+        let html_url = format!("{}/pull/{}", repo.html_url, number);
+        let issue_url =
+            format!("{}/repos/{}/issues/{}", API_URL, repo.full_name, number);
+
+        PullRequest {
+            id: PullRequestId(fake_id(&url)),
+            diff_url: format!("{}.diff", html_url),
+            patch_url: format!("{}.patch", html_url),
+            commits_url: format!("{}/commits", url),
+            review_comments_url: format!("{}/comments", url),
+            review_comment_url: format!(
+                "{}/repos/{}/pulls/comments{{/number}}",
+                API_URL, repo.full_name
+            ),
+            comments_url: format!("{}/comments", issue_url),
+            statuses_url: format!(
+                "{}/repos/{}/statuses/{}",
+                API_URL,
+                repo.full_name,
+                fake_oid(&format!("{}:feature-{}", repo.full_name, number))
+            ),
+            number,
+            state: "open".to_string(),
+            title: format!("Pull request #{}", number),
+            body: None,
+            created_at: fake_time(),
+            updated_at: fake_time(),
+            closed_at: None,
+            merged_at: None,
+            head: ShortCommit::fake(repo, &format!("feature-{}", number)),
+            base: ShortCommit::fake(repo, &repo.default_branch),
+            user: repo.owner.clone(),
+            assignee: None,
+            assignees: Vec::new(),
+            merge_commit_sha: None,
+            merged: false,
+            mergeable: None,
+            merged_by: None,
+            comments: Some(0),
+            commits: Some(1),
+            additions: Some(0),
+            deletions: Some(0),
+            changed_files: Some(0),
+            labels: Vec::new(),
+            url,
+            html_url,
+            issue_url,
+        }
+    }
+}
+
+impl Issue {
+    /// Creates an open issue in a repository, opened by the owner of the
+    /// repository.
+    pub fn fake(repo: &Repository, number: IssueNumber) -> Self {
+        let url =
+            format!("{}/repos/{}/issues/{}", API_URL, repo.full_name, number);
+
+        Issue {
+            id: IssueId(fake_id(&url)),
+            labels_url: format!("{}/labels{{/name}}", url),
+            comments_url: format!("{}/comments", url),
+            events_url: format!("{}/events", url),
+            html_url: format!("{}/issues/{}", repo.html_url, number),
+            number,
+            state: "open".to_string(),
+            title: format!("Issue #{}", number),
+            body: None,
+            user: repo.owner.clone(),
+            labels: Vec::new(),
+            assignee: None,
+            locked: false,
+            comments: 0,
+            pull_request: None,
+            closed_at: None,
+            created_at: fake_time(),
+            updated_at: fake_time(),
+            assignees: Vec::new(),
+            url,
+        }
+    }
+}
+
+impl PushCommit {
+    /// Creates a commit in a repository, authored by the owner of the
+    /// repository. The SHA is derived from the message.
+    pub fn fake(repo: &Repository, message: &str) -> Self {
+        let id = fake_oid(&format!("{}:{}", repo.full_name, message));
+        let author = PushAuthor {
+            name: repo.owner.login.clone(),
+            email: Some(format!(
+                "{}@users.noreply.github.com",
+                repo.owner.login
+            )),
+            username: Some(repo.owner.login.clone()),
+        };
+
+        PushCommit {
+            id,
+            tree_id: fake_oid(&format!("{}:tree", id)),
+            distinct: true,
+            message: message.to_string(),
+            timestamp: fake_time(),
+            url: format!("{}/commit/{}", repo.html_url, id),
+            author: author.clone(),
+            committer: author,
+            added: Vec::new(),
+            removed: Vec::new(),
+            modified: Vec::new(),
+        }
+    }
+}
+
+impl PushEvent {
+    /// Returns a builder for a push event.
+    ///
+    /// By default, this is a push of no commits to the `master` branch of
+    /// `octocat/hello-world` by the repository owner.
+    pub fn builder() -> PushEventBuilder {
+        PushEventBuilder::new()
+    }
+}
+
+/// A builder for `PushEvent`.
+#[derive(Debug, Clone)]
+pub struct PushEventBuilder {
+    git_ref: String,
+    before: Option<Oid>,
+    after: Option<Oid>,
+    created: bool,
+    deleted: bool,
+    forced: bool,
+    base_ref: Option<String>,
+    commits: Vec<PushCommit>,
+    repository: Repository,
+    sender: Option<User>,
+    installation: Option<InstallationId>,
+}
+
+impl PushEventBuilder {
+    fn new() -> Self {
+        PushEventBuilder {
+            git_ref: "refs/heads/master".to_string(),
+            before: None,
+            after: None,
+            created: false,
+            deleted: false,
+            forced: false,
+            base_ref: None,
+            commits: Vec::new(),
+            repository: Repository::fake("octocat/hello-world"),
+            sender: None,
+            installation: None,
+        }
+    }
+
+    /// Sets the repository that was pushed to.
+    pub fn repository(mut self, repository: Repository) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Sets the full Git ref that was pushed (e.g., `refs/tags/v1.0`).
+    pub fn git_ref<S: Into<String>>(mut self, git_ref: S) -> Self {
+        self.git_ref = git_ref.into();
+        self
+    }
+
+    /// Sets the branch that was pushed.
+    pub fn branch(self, branch: &str) -> Self {
+        self.git_ref(format!("refs/heads/{}", branch))
+    }
+
+    /// Sets the commit the branch pointed to before the push.
+    pub fn before(mut self, before: Oid) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Sets the commit the branch points to after the push. By default, this
+    /// is the last commit added with `commit`.
+    pub fn after(mut self, after: Oid) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Marks the push as creating the branch.
+    pub fn created(mut self, created: bool) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Marks the push as deleting the branch.
+    pub fn deleted(mut self, deleted: bool) -> Self {
+        self.deleted = deleted;
+        self
+    }
+
+    /// Marks the push as a force-push.
+    pub fn forced(mut self, forced: bool) -> Self {
+        self.forced = forced;
+        self
+    }
+
+    pub fn base_ref<S: Into<String>>(mut self, base_ref: S) -> Self {
+        self.base_ref = Some(base_ref.into());
+        self
+    }
+
+    /// Adds a commit to the push.
+    pub fn commit(mut self, commit: PushCommit) -> Self {
+        self.commits.push(commit);
+        self
+    }
+
+    /// Adds a commit with the given message to the push. See
+    /// `PushCommit::fake`.
+    pub fn commit_message(self, message: &str) -> Self {
+        let commit = PushCommit::fake(&self.repository, message);
+        self.commit(commit)
+    }
+
+    /// Sets the user who pushed. By default, this is the repository owner.
+    pub fn sender(mut self, sender: User) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sets the App installation ID.
+    pub fn installation(mut self, id: InstallationId) -> Self {
+        self.installation = Some(id);
+        self
+    }
+
+    pub fn build(self) -> PushEvent {
+        let head_commit = self.commits.last().cloned();
+
+        let before = self
+            .before
+            .unwrap_or_else(|| fake_oid(&format!("{}:before", self.git_ref)));
+
+        let after = if self.deleted {
+            Oid::ZERO
+        } else {
+            self.after
+                .or_else(|| head_commit.as_ref().map(|c| c.id))
+                .unwrap_or_else(|| fake_oid(&format!("{}:after", self.git_ref)))
+        };
+
+        let before = if self.created { Oid::ZERO } else { before };
+
+        let repository = self.repository;
+        let sender = self.sender.unwrap_or_else(|| repository.owner.clone());
+
+        let compare = format!(
+            "{}/compare/{}...{}",
+            repository.html_url,
+            &before.to_string()[..12],
+            &after.to_string()[..12]
+        );
+
+        PushEvent {
+            git_ref: self.git_ref,
+            before,
+            after,
+            created: self.created,
+            deleted: self.deleted,
+            forced: self.forced,
+            base_ref: self.base_ref,
+            compare,
+            commits: self.commits,
+            head_commit,
+            repository,
+            pusher: Pusher {
+                name: sender.login.clone(),
+                email: Some(format!(
+                    "{}@users.noreply.github.com",
+                    sender.login
+                )),
+            },
+            sender,
+            installation: self.installation.map(|id| ShortInstallation { id }),
+        }
+    }
+}
+
+impl PullRequestEvent {
+    /// Returns a builder for a pull request event with the given action.
+    ///
+    /// By default, the pull request is #1 in `octocat/hello-world` and the
+    /// sender is the repository owner.
+    pub fn builder(action: PullRequestAction) -> PullRequestEventBuilder {
+        PullRequestEventBuilder::new(action)
+    }
+}
+
+/// A builder for `PullRequestEvent`.
+#[derive(Debug, Clone)]
+pub struct PullRequestEventBuilder {
+    action: PullRequestAction,
+    repository: Repository,
+    pull_request: Option<PullRequest>,
+    label: Option<Label>,
+    requested_reviewer: Option<User>,
+    assignee: Option<User>,
+    sender: Option<User>,
+    installation: Option<InstallationId>,
+}
+
+impl PullRequestEventBuilder {
+    fn new(action: PullRequestAction) -> Self {
+        PullRequestEventBuilder {
+            action,
+            repository: Repository::fake("octocat/hello-world"),
+            pull_request: None,
+            label: None,
+            requested_reviewer: None,
+            assignee: None,
+            sender: None,
+            installation: None,
+        }
+    }
+
+    /// Sets the repository of the pull request.
+    pub fn repository(mut self, repository: Repository) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Sets the pull request. By default, this is `PullRequest::fake` with
+    /// number 1 in the repository.
+    pub fn pull_request(mut self, pull_request: PullRequest) -> Self {
+        self.pull_request = Some(pull_request);
+        self
+    }
+
+    /// Sets the label that was added or removed.
+    pub fn label(mut self, label: Label) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Sets the user whose review was requested or removed.
+    pub fn requested_reviewer(mut self, reviewer: User) -> Self {
+        self.requested_reviewer = Some(reviewer);
+        self
+    }
+
+    /// Sets the user who was assigned or unassigned.
+    pub fn assignee(mut self, assignee: User) -> Self {
+        self.assignee = Some(assignee);
+        self
+    }
+
+    /// Sets the user who triggered the event. By default, this is the
+    /// repository owner.
+    pub fn sender(mut self, sender: User) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Sets the App installation ID.
+    pub fn installation(mut self, id: InstallationId) -> Self {
+        self.installation = Some(id);
+        self
+    }
+
+    pub fn build(self) -> PullRequestEvent {
+        let repository = self.repository;
+
+        let pull_request = self
+            .pull_request
+            .unwrap_or_else(|| PullRequest::fake(&repository, IssueNumber(1)));
+
+        let sender = self.sender.unwrap_or_else(|| repository.owner.clone());
+
+        let (before, after) = match self.action {
+            PullRequestAction::Synchronize => (
+                Some(fake_oid(&format!("{}:before", pull_request.url))),
+                Some(pull_request.head.sha),
+            ),
+            _ => (None, None),
+        };
+
+        PullRequestEvent {
+            action: self.action,
+            number: pull_request.number,
+            pull_request,
+            changes: None,
+            requested_reviewer: self.requested_reviewer,
+            requested_team: None,
+            label: self.label,
+            assignee: self.assignee,
+            milestone: None,
+            reason: None,
+            before,
+            after,
+            repository,
+            sender,
+            installation: self.installation.map(|id| ShortInstallation { id }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use std::fmt::Debug;
+
+    use crate::Event;
+
+    fn round_trip<T>(value: &T)
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+    }
+
+    /// Checks that the event survives a round trip through its webhook
+    /// payload.
+    fn event_round_trip(event: Event) {
+        let mut json = Vec::new();
+        event
+            .serialize_payload(&mut serde_json::Serializer::new(&mut json))
+            .unwrap();
+        let json = String::from_utf8(json).unwrap();
+
+        assert_eq!(Event::from_json(event.event_type(), &json).unwrap(), event);
+    }
+
+    #[test]
+    fn fakes() {
+        let repo = Repository::fake("octocat/hello-world");
+        assert_eq!(repo.owner.login, "octocat");
+        assert_eq!(repo, Repository::fake("octocat/hello-world"));
+
+        round_trip(&User::fake("octocat"));
+        round_trip(&repo);
+        round_trip(&Label::fake(&repo, "bug"));
+        round_trip(&ShortCommit::fake(&repo, "master"));
+        round_trip(&PullRequest::fake(&repo, IssueNumber(1)));
+        round_trip(&Issue::fake(&repo, IssueNumber(2)));
+        round_trip(&PushCommit::fake(&repo, "Initial commit"));
+    }
+
+    #[test]
+    fn push_event() {
+        let default = PushEvent::builder().build();
+        assert_eq!(default.git_ref, "refs/heads/master");
+        assert_eq!(default.head_commit, None);
+        event_round_trip(Event::Push(default));
+
+        let event = PushEvent::builder()
+            .repository(Repository::fake("octo-org/octo-repo"))
+            .branch("feature")
+            .commit_message("First")
+            .commit_message("Second")
+            .sender(User::fake("hubot"))
+            .installation(InstallationId(1))
+            .build();
+        assert_eq!(event.commits.len(), 2);
+        assert_eq!(event.after, event.commits[1].id);
+        assert_eq!(event.head_commit.as_ref(), event.commits.last());
+        assert_eq!(event.pusher.name, "hubot");
+        event_round_trip(Event::Push(event));
+
+        let created = PushEvent::builder().created(true).build();
+        assert_eq!(created.before, Oid::ZERO);
+        event_round_trip(Event::Push(created));
+
+        let deleted = PushEvent::builder().deleted(true).build();
+        assert_eq!(deleted.after, Oid::ZERO);
+        event_round_trip(Event::Push(deleted));
+    }
+
+    #[test]
+    fn pull_request_event() {
+        let repo = Repository::fake("octo-org/octo-repo");
+
+        let opened = PullRequestEvent::builder(PullRequestAction::Opened)
+            .repository(repo.clone())
+            .build();
+        assert_eq!(opened.number, IssueNumber(1));
+        assert_eq!(opened.before, None);
+        event_round_trip(Event::PullRequest(opened));
+
+        let synchronize =
+            PullRequestEvent::builder(PullRequestAction::Synchronize)
+                .pull_request(PullRequest::fake(&repo, IssueNumber(7)))
+                .repository(repo.clone())
+                .installation(InstallationId(1))
+                .build();
+        assert_eq!(synchronize.number, IssueNumber(7));
+        assert_eq!(synchronize.after, Some(synchronize.pull_request.head.sha));
+        assert!(synchronize.before.is_some());
+        event_round_trip(Event::PullRequest(synchronize));
+
+        let labeled = PullRequestEvent::builder(PullRequestAction::Labeled)
+            .label(Label::fake(&repo, "bug"))
+            .build();
+        event_round_trip(Event::PullRequest(labeled));
+
+        let requested =
+            PullRequestEvent::builder(PullRequestAction::ReviewRequested)
+                .requested_reviewer(User::fake("hubot"))
+                .sender(User::fake("octocat"))
+                .build();
+        event_round_trip(Event::PullRequest(requested));
+
+        let assigned = PullRequestEvent::builder(PullRequestAction::Assigned)
+            .assignee(User::fake("hubot"))
+            .build();
+        event_round_trip(Event::PullRequest(assigned));
+    }
+}
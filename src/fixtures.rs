@@ -0,0 +1,479 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Sample webhook payloads for every supported event type and action.
+//!
+//! The payloads are anonymized copies of what GitHub sends, using the same
+//! `Codertocat/Hello-World` placeholders as GitHub's own documentation. They
+//! are useful as ready-made inputs when testing webhook handlers.
+//!
+//! ```
+//! use github_types::{fixtures, AppEvent, EventType, InstallationId};
+//!
+//! let fixture =
+//!     fixtures::get(EventType::PullRequest, Some("opened")).unwrap();
+//! let event = fixture.event();
+//! assert_eq!(event.event_type(), EventType::PullRequest);
+//! assert_eq!(event.installation(), Some(InstallationId(2311213)));
+//! ```
+
+use crate::{Event, EventType};
+
+/// A sample webhook payload.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Fixture {
+    /// The event type, as sent in the `X-GitHub-Event` header.
+    pub event_type: EventType,
+
+    /// The `action` of the payload. This is `None` for events that don't have
+    /// an action, such as `push`.
+    pub action: Option<&'static str>,
+
+    /// The JSON body of the webhook.
+    pub payload: &'static str,
+}
+
+impl Fixture {
+    /// Parses the payload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the payload fails to deserialize. This never happens for the
+    /// bundled fixtures.
+    pub fn event(&self) -> Event {
+        Event::from_json(self.event_type, self.payload).unwrap_or_else(|e| {
+            panic!("invalid fixture {}: {}", self.name(), e)
+        })
+    }
+
+    /// Returns the name of the fixture, such as `pull_request.opened` or
+    /// `push`.
+    pub fn name(&self) -> String {
+        match self.action {
+            Some(action) => format!("{}.{}", self.event_type, action),
+            None => self.event_type.to_string(),
+        }
+    }
+}
+
+macro_rules! fixture {
+    ($event_type:ident, $dir:literal) => {
+        Fixture {
+            event_type: EventType::$event_type,
+            action: None,
+            payload: include_str!(concat!("../fixtures/", $dir, ".json")),
+        }
+    };
+    ($event_type:ident, $dir:literal, $action:literal) => {
+        Fixture {
+            event_type: EventType::$event_type,
+            action: Some($action),
+            payload: include_str!(concat!(
+                "../fixtures/",
+                $dir,
+                "/",
+                $action,
+                ".json"
+            )),
+        }
+    };
+}
+
+/// All of the bundled fixtures, ordered by event type and action.
+pub const FIXTURES: &[Fixture] = &[
+    fixture!(CheckRun, "check_run", "completed"),
+    fixture!(CheckRun, "check_run", "created"),
+    fixture!(CheckRun, "check_run", "requested_action"),
+    fixture!(CheckRun, "check_run", "rerequested"),
+    fixture!(CheckSuite, "check_suite", "completed"),
+    fixture!(CheckSuite, "check_suite", "requested"),
+    fixture!(CheckSuite, "check_suite", "rerequested"),
+    fixture!(CommitComment, "commit_comment", "created"),
+    fixture!(Create, "create"),
+    fixture!(Delete, "delete"),
+    fixture!(DeployKey, "deploy_key", "created"),
+    fixture!(DeployKey, "deploy_key", "deleted"),
+    fixture!(
+        GitHubAppAuthorization,
+        "github_app_authorization",
+        "revoked"
+    ),
+    fixture!(Gollum, "gollum"),
+    fixture!(Installation, "installation", "created"),
+    fixture!(Installation, "installation", "deleted"),
+    fixture!(Installation, "installation", "new_permissions_accepted"),
+    fixture!(
+        InstallationRepositories,
+        "installation_repositories",
+        "added"
+    ),
+    fixture!(
+        InstallationRepositories,
+        "installation_repositories",
+        "removed"
+    ),
+    fixture!(
+        IntegrationInstallation,
+        "integration_installation",
+        "created"
+    ),
+    fixture!(
+        IntegrationInstallation,
+        "integration_installation",
+        "deleted"
+    ),
+    fixture!(
+        IntegrationInstallationRepositories,
+        "integration_installation_repositories",
+        "added"
+    ),
+    fixture!(
+        IntegrationInstallationRepositories,
+        "integration_installation_repositories",
+        "removed"
+    ),
+    fixture!(IssueComment, "issue_comment", "created"),
+    fixture!(IssueComment, "issue_comment", "deleted"),
+    fixture!(IssueComment, "issue_comment", "edited"),
+    fixture!(Issues, "issues", "assigned"),
+    fixture!(Issues, "issues", "closed"),
+    fixture!(Issues, "issues", "deleted"),
+    fixture!(Issues, "issues", "demilestoned"),
+    fixture!(Issues, "issues", "edited"),
+    fixture!(Issues, "issues", "labeled"),
+    fixture!(Issues, "issues", "milestoned"),
+    fixture!(Issues, "issues", "opened"),
+    fixture!(Issues, "issues", "pinned"),
+    fixture!(Issues, "issues", "reopened"),
+    fixture!(Issues, "issues", "transferred"),
+    fixture!(Issues, "issues", "unassigned"),
+    fixture!(Issues, "issues", "unlabeled"),
+    fixture!(Issues, "issues", "unpinned"),
+    fixture!(Label, "label", "created"),
+    fixture!(Label, "label", "deleted"),
+    fixture!(Label, "label", "edited"),
+    fixture!(Meta, "meta", "deleted"),
+    fixture!(Package, "package", "published"),
+    fixture!(Package, "package", "updated"),
+    fixture!(Ping, "ping"),
+    fixture!(PullRequest, "pull_request", "assigned"),
+    fixture!(PullRequest, "pull_request", "auto_merge_disabled"),
+    fixture!(PullRequest, "pull_request", "auto_merge_enabled"),
+    fixture!(PullRequest, "pull_request", "closed"),
+    fixture!(PullRequest, "pull_request", "converted_to_draft"),
+    fixture!(PullRequest, "pull_request", "demilestoned"),
+    fixture!(PullRequest, "pull_request", "dequeued"),
+    fixture!(PullRequest, "pull_request", "edited"),
+    fixture!(PullRequest, "pull_request", "enqueued"),
+    fixture!(PullRequest, "pull_request", "labeled"),
+    fixture!(PullRequest, "pull_request", "locked"),
+    fixture!(PullRequest, "pull_request", "milestoned"),
+    fixture!(PullRequest, "pull_request", "opened"),
+    fixture!(PullRequest, "pull_request", "ready_for_review"),
+    fixture!(PullRequest, "pull_request", "reopened"),
+    fixture!(PullRequest, "pull_request", "review_request_removed"),
+    fixture!(PullRequest, "pull_request", "review_requested"),
+    fixture!(PullRequest, "pull_request", "synchronize"),
+    fixture!(PullRequest, "pull_request", "unassigned"),
+    fixture!(PullRequest, "pull_request", "unlabeled"),
+    fixture!(PullRequest, "pull_request", "unlocked"),
+    fixture!(PullRequestReview, "pull_request_review", "dismissed"),
+    fixture!(PullRequestReview, "pull_request_review", "edited"),
+    fixture!(PullRequestReview, "pull_request_review", "submitted"),
+    fixture!(
+        PullRequestReviewComment,
+        "pull_request_review_comment",
+        "created"
+    ),
+    fixture!(
+        PullRequestReviewComment,
+        "pull_request_review_comment",
+        "deleted"
+    ),
+    fixture!(
+        PullRequestReviewComment,
+        "pull_request_review_comment",
+        "edited"
+    ),
+    fixture!(
+        PullRequestReviewThread,
+        "pull_request_review_thread",
+        "resolved"
+    ),
+    fixture!(
+        PullRequestReviewThread,
+        "pull_request_review_thread",
+        "unresolved"
+    ),
+    fixture!(Push, "push"),
+    fixture!(RegistryPackage, "registry_package", "published"),
+    fixture!(RegistryPackage, "registry_package", "updated"),
+    fixture!(Repository, "repository", "archived"),
+    fixture!(Repository, "repository", "created"),
+    fixture!(Repository, "repository", "deleted"),
+    fixture!(Repository, "repository", "privatized"),
+    fixture!(Repository, "repository", "publicized"),
+    fixture!(Repository, "repository", "unarchived"),
+    fixture!(
+        RepositoryDispatch,
+        "repository_dispatch",
+        "deploy-production"
+    ),
+    fixture!(RepositoryRuleset, "repository_ruleset", "created"),
+    fixture!(RepositoryRuleset, "repository_ruleset", "deleted"),
+    fixture!(RepositoryRuleset, "repository_ruleset", "edited"),
+    fixture!(Star, "star", "created"),
+    fixture!(Star, "star", "deleted"),
+    fixture!(Watch, "watch", "started"),
+];
+
+/// Returns the fixture for the given event type and action.
+pub fn get(
+    event_type: EventType,
+    action: Option<&str>,
+) -> Option<&'static Fixture> {
+    FIXTURES
+        .iter()
+        .find(|f| f.event_type == event_type && f.action == action)
+}
+
+/// Returns all fixtures for the given event type.
+pub fn all(event_type: EventType) -> impl Iterator<Item = &'static Fixture> {
+    FIXTURES.iter().filter(move |f| f.event_type == event_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::Value;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn deserializes_to_matching_variant() {
+        for fixture in FIXTURES {
+            assert_eq!(
+                fixture.event().event_type(),
+                fixture.event_type,
+                "{}",
+                fixture.name()
+            );
+        }
+    }
+
+    #[test]
+    fn action_matches_payload() {
+        for fixture in FIXTURES {
+            let value: serde_json::Value =
+                serde_json::from_str(fixture.payload).unwrap();
+            assert_eq!(
+                value.get("action").and_then(|a| a.as_str()),
+                fixture.action,
+                "{}",
+                fixture.name()
+            );
+        }
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        for fixture in FIXTURES {
+            let event = fixture.event();
+            let mut json = Vec::new();
+            event
+                .serialize_payload(&mut serde_json::Serializer::new(&mut json))
+                .unwrap();
+            let json = String::from_utf8(json).unwrap();
+            let parsed = Event::from_json(fixture.event_type, &json).unwrap();
+            assert_eq!(parsed, event, "{}", fixture.name());
+        }
+    }
+
+    /// A key or index in a JSON document.
+    #[derive(Debug)]
+    enum Segment {
+        Key(String),
+        Index(usize),
+    }
+
+    fn segments(path: &serde_ignored::Path<'_>) -> Vec<Segment> {
+        use serde_ignored::Path;
+
+        let mut segments = match path {
+            Path::Root => return Vec::new(),
+            Path::Seq { parent, .. }
+            | Path::Map { parent, .. }
+            | Path::Some { parent }
+            | Path::NewtypeStruct { parent }
+            | Path::NewtypeVariant { parent } => segments(parent),
+        };
+
+        match path {
+            Path::Seq { index, .. } => segments.push(Segment::Index(*index)),
+            Path::Map { key, .. } => segments.push(Segment::Key(key.clone())),
+            _ => {}
+        }
+
+        segments
+    }
+
+    fn remove(value: &mut Value, path: &[Segment]) {
+        let (last, parents) = path.split_last().unwrap();
+
+        let parent =
+            parents.iter().fold(value, |value, segment| match segment {
+                Segment::Key(key) => &mut value[key.as_str()],
+                Segment::Index(index) => &mut value[*index],
+            });
+
+        match last {
+            Segment::Key(key) => {
+                parent.as_object_mut().unwrap().remove(key);
+            }
+            Segment::Index(_) => unreachable!("arrays are never ignored"),
+        }
+    }
+
+    /// Lists the paths at which two JSON documents differ.
+    fn diff(path: &str, a: &Value, b: &Value, out: &mut Vec<String>) {
+        match (a, b) {
+            (Value::Object(a), Value::Object(b)) => {
+                for (key, value) in a {
+                    let path = format!("{}.{}", path, key);
+                    match b.get(key) {
+                        Some(other) => diff(&path, value, other, out),
+                        None => out.push(format!("{} only in output", path)),
+                    }
+                }
+
+                for key in b.keys().filter(|key| !a.contains_key(*key)) {
+                    out.push(format!("{}.{} only in fixture", path, key));
+                }
+            }
+            (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+                for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                    diff(&format!("{}.{}", path, i), a, b, out);
+                }
+            }
+            _ if a == b => {}
+            _ => out.push(format!("{}: {} != {}", path, a, b)),
+        }
+    }
+
+    /// Everything that is deserialized must be serialized exactly as GitHub
+    /// sent it. Fields that aren't modeled are ignored.
+    #[test]
+    fn payload_serializes_as_sent() {
+        let mut failures = Vec::new();
+
+        for fixture in FIXTURES {
+            let mut expected: Value =
+                serde_json::from_str(fixture.payload).unwrap();
+
+            let mut ignored = Vec::new();
+            let mut callback =
+                |path: serde_ignored::Path<'_>| ignored.push(segments(&path));
+            let mut deserializer =
+                serde_json::Deserializer::from_str(fixture.payload);
+            let event = Event::deserialize_payload(
+                fixture.event_type,
+                serde_ignored::Deserializer::new(
+                    &mut deserializer,
+                    &mut callback,
+                ),
+            )
+            .unwrap();
+
+            for path in &ignored {
+                remove(&mut expected, path);
+            }
+
+            let actual = event
+                .serialize_payload(serde_json::value::Serializer)
+                .unwrap();
+
+            let mut differences = Vec::new();
+            diff("", &actual, &expected, &mut differences);
+
+            if !differences.is_empty() {
+                failures.push(format!(
+                    "{}:\n    {}",
+                    fixture.name(),
+                    differences.join("\n    ")
+                ));
+            }
+        }
+
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let mut seen = HashSet::new();
+        for fixture in FIXTURES {
+            assert!(seen.insert(fixture.name()), "{}", fixture.name());
+        }
+    }
+
+    #[test]
+    fn every_event_type_is_covered() {
+        let supported = [
+            EventType::Ping,
+            EventType::CheckRun,
+            EventType::CheckSuite,
+            EventType::CommitComment,
+            EventType::Create,
+            EventType::Delete,
+            EventType::DeployKey,
+            EventType::GitHubAppAuthorization,
+            EventType::Gollum,
+            EventType::Installation,
+            EventType::InstallationRepositories,
+            EventType::IntegrationInstallation,
+            EventType::IntegrationInstallationRepositories,
+            EventType::IssueComment,
+            EventType::Issues,
+            EventType::Label,
+            EventType::Meta,
+            EventType::Package,
+            EventType::PullRequest,
+            EventType::PullRequestReview,
+            EventType::PullRequestReviewComment,
+            EventType::PullRequestReviewThread,
+            EventType::Push,
+            EventType::RegistryPackage,
+            EventType::Repository,
+            EventType::RepositoryDispatch,
+            EventType::RepositoryRuleset,
+            EventType::Star,
+            EventType::Watch,
+        ];
+
+        for event_type in &supported {
+            assert!(all(*event_type).next().is_some(), "{}", event_type);
+        }
+    }
+
+    #[test]
+    fn unsupported_event_type() {
+        assert!(Event::from_json(EventType::Fork, "{}").is_err());
+    }
+}
@@ -0,0 +1,994 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Types generated from GitHub's OpenAPI description.
+//!
+//! This file is generated by `cargo xtask codegen`. Do not edit it by hand.
+//! See `openapi/README.md`.
+//!
+//! Free-form JSON is represented as `ClientPayload` so that it can be ordered
+//! and hashed. Types with floating point fields only derive `PartialEq`, since
+//! floats have no total order.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    App, CheckRunStatus, CheckSuiteId, ClientPayload, Conclusion, DateTime,
+    IssueNumber, Oid, RepoId, User,
+};
+
+/// Code Of Conduct
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct CodeOfConduct {
+    pub body: Option<String>,
+    pub html_url: Option<String>,
+    pub key: String,
+    pub name: String,
+    pub url: String,
+}
+
+/// A request for a specific ref(branch,sha,tag) to be deployed
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Deployment {
+    pub created_at: DateTime,
+
+    pub creator: Option<User>,
+
+    pub description: Option<String>,
+
+    /// Name for the target deployment environment.
+    pub environment: String,
+
+    /// Unique identifier of the deployment
+    pub id: u64,
+
+    pub node_id: String,
+
+    pub original_environment: Option<String>,
+
+    pub payload: ClientPayload,
+
+    pub performed_via_github_app: Option<App>,
+
+    /// Specifies if the given environment is one that end-users directly
+    /// interact with. Default: false.
+    pub production_environment: Option<bool>,
+
+    /// The ref to deploy. This can be a branch, tag, or sha.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+
+    pub repository_url: String,
+
+    pub sha: Oid,
+
+    pub statuses_url: String,
+
+    /// Parameter to specify a task to execute
+    pub task: String,
+
+    /// Specifies if the given environment is will no longer exist at some
+    /// point in the future. Default: false.
+    pub transient_environment: Option<bool>,
+
+    pub updated_at: DateTime,
+
+    pub url: String,
+}
+
+/// The status of a deployment.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeploymentStatus {
+    pub created_at: DateTime,
+
+    pub creator: Option<User>,
+
+    pub deployment_url: String,
+
+    /// A short description of the status.
+    pub description: String,
+
+    /// The environment of the deployment that the status is for.
+    pub environment: Option<String>,
+
+    /// The URL for accessing your environment.
+    pub environment_url: Option<String>,
+
+    pub id: u64,
+
+    /// The URL to associate with this status.
+    pub log_url: Option<String>,
+
+    pub node_id: String,
+
+    pub performed_via_github_app: Option<App>,
+
+    pub repository_url: String,
+
+    /// The state of the status.
+    pub state: DeploymentStatusState,
+
+    /// Deprecated: the URL to associate with this status.
+    pub target_url: String,
+
+    pub updated_at: DateTime,
+
+    pub url: String,
+}
+
+/// The state of the status.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentStatusState {
+    Error,
+    Failure,
+    Inactive,
+    Pending,
+    Success,
+    Queued,
+    InProgress,
+}
+
+/// Information of a job execution in a workflow run
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Job {
+    pub check_run_url: String,
+
+    /// The time that the job finished, in ISO 8601 format.
+    pub completed_at: Option<DateTime>,
+
+    /// The outcome of the job.
+    pub conclusion: Option<Conclusion>,
+
+    /// The time that the job created, in ISO 8601 format.
+    pub created_at: DateTime,
+
+    /// The name of the current branch.
+    pub head_branch: Option<String>,
+
+    /// The SHA of the commit that is being run.
+    pub head_sha: Oid,
+
+    pub html_url: Option<String>,
+
+    /// The id of the job.
+    pub id: u64,
+
+    /// Labels for the workflow job. Specified by the "runs_on" attribute in
+    /// the action's workflow file.
+    pub labels: Vec<String>,
+
+    /// The name of the job.
+    pub name: String,
+
+    pub node_id: String,
+
+    /// Attempt number of the associated workflow run, 1 for first attempt and
+    /// higher if the workflow was re-run.
+    pub run_attempt: Option<u64>,
+
+    /// The id of the associated workflow run.
+    pub run_id: u64,
+
+    pub run_url: String,
+
+    /// The ID of the runner group to which this job has been assigned. (If a
+    /// runner hasn't yet been assigned, this will be null.)
+    pub runner_group_id: Option<u64>,
+
+    /// The name of the runner group to which this job has been assigned. (If a
+    /// runner hasn't yet been assigned, this will be null.)
+    pub runner_group_name: Option<String>,
+
+    /// The ID of the runner to which this job has been assigned. (If a runner
+    /// hasn't yet been assigned, this will be null.)
+    pub runner_id: Option<u64>,
+
+    /// The name of the runner to which this job has been assigned. (If a
+    /// runner hasn't yet been assigned, this will be null.)
+    pub runner_name: Option<String>,
+
+    /// The time that the job started, in ISO 8601 format.
+    pub started_at: DateTime,
+
+    /// The phase of the lifecycle that the job is currently in.
+    pub status: CheckRunStatus,
+
+    /// Steps in this job.
+    pub steps: Option<Vec<JobStepsItem>>,
+
+    pub url: String,
+
+    /// The name of the workflow.
+    pub workflow_name: Option<String>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct JobStepsItem {
+    /// The time that the job finished, in ISO 8601 format.
+    pub completed_at: Option<DateTime>,
+
+    /// The outcome of the job.
+    pub conclusion: Option<Conclusion>,
+
+    /// The name of the job.
+    pub name: String,
+
+    pub number: u64,
+
+    /// The time that the step started, in ISO 8601 format.
+    pub started_at: Option<DateTime>,
+
+    /// The phase of the lifecycle that the job is currently in.
+    pub status: CheckRunStatus,
+}
+
+/// Minimal Repository
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MinimalRepository {
+    pub allow_forking: Option<bool>,
+
+    pub archive_url: String,
+
+    pub archived: Option<bool>,
+
+    pub assignees_url: String,
+
+    pub blobs_url: String,
+
+    pub branches_url: String,
+
+    pub clone_url: Option<String>,
+
+    pub code_of_conduct: Option<CodeOfConduct>,
+
+    pub collaborators_url: String,
+
+    pub comments_url: String,
+
+    pub commits_url: String,
+
+    pub compare_url: String,
+
+    pub contents_url: String,
+
+    pub contributors_url: String,
+
+    pub created_at: Option<DateTime>,
+
+    pub default_branch: Option<String>,
+
+    pub delete_branch_on_merge: Option<bool>,
+
+    pub deployments_url: String,
+
+    pub description: Option<String>,
+
+    pub disabled: Option<bool>,
+
+    pub downloads_url: String,
+
+    pub events_url: String,
+
+    pub fork: bool,
+
+    pub forks: Option<u64>,
+
+    pub forks_count: Option<u64>,
+
+    pub forks_url: String,
+
+    pub full_name: String,
+
+    pub git_commits_url: String,
+
+    pub git_refs_url: String,
+
+    pub git_tags_url: String,
+
+    pub git_url: Option<String>,
+
+    pub has_discussions: Option<bool>,
+
+    pub has_downloads: Option<bool>,
+
+    pub has_issues: Option<bool>,
+
+    pub has_pages: Option<bool>,
+
+    pub has_projects: Option<bool>,
+
+    pub has_wiki: Option<bool>,
+
+    pub homepage: Option<String>,
+
+    pub hooks_url: String,
+
+    pub html_url: String,
+
+    pub id: RepoId,
+
+    pub is_template: Option<bool>,
+
+    pub issue_comment_url: String,
+
+    pub issue_events_url: String,
+
+    pub issues_url: String,
+
+    pub keys_url: String,
+
+    pub labels_url: String,
+
+    pub language: Option<String>,
+
+    pub languages_url: String,
+
+    pub license: Option<MinimalRepositoryLicense>,
+
+    pub merges_url: String,
+
+    pub milestones_url: String,
+
+    pub mirror_url: Option<String>,
+
+    pub name: String,
+
+    pub network_count: Option<u64>,
+
+    pub node_id: String,
+
+    pub notifications_url: String,
+
+    pub open_issues: Option<u64>,
+
+    pub open_issues_count: Option<u64>,
+
+    pub owner: User,
+
+    pub permissions: Option<MinimalRepositoryPermissions>,
+
+    pub private: bool,
+
+    pub pulls_url: String,
+
+    pub pushed_at: Option<DateTime>,
+
+    pub releases_url: String,
+
+    pub role_name: Option<String>,
+
+    pub security_and_analysis: Option<SecurityAndAnalysis>,
+
+    /// The size of the repository, in kilobytes. Size is calculated hourly.
+    /// When a repository is initially created, the size is 0.
+    pub size: Option<u64>,
+
+    pub ssh_url: Option<String>,
+
+    pub stargazers_count: Option<u64>,
+
+    pub stargazers_url: String,
+
+    pub statuses_url: String,
+
+    pub subscribers_count: Option<u64>,
+
+    pub subscribers_url: String,
+
+    pub subscription_url: String,
+
+    pub svn_url: Option<String>,
+
+    pub tags_url: String,
+
+    pub teams_url: String,
+
+    pub temp_clone_token: Option<String>,
+
+    pub topics: Option<Vec<String>>,
+
+    pub trees_url: String,
+
+    pub updated_at: Option<DateTime>,
+
+    pub url: String,
+
+    pub visibility: Option<String>,
+
+    pub watchers: Option<u64>,
+
+    pub watchers_count: Option<u64>,
+
+    pub web_commit_signoff_required: Option<bool>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MinimalRepositoryLicense {
+    pub key: Option<String>,
+    pub name: Option<String>,
+    pub node_id: Option<String>,
+    pub spdx_id: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MinimalRepositoryPermissions {
+    pub admin: Option<bool>,
+    pub maintain: Option<bool>,
+    pub pull: Option<bool>,
+    pub push: Option<bool>,
+    pub triage: Option<bool>,
+}
+
+/// A commit.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NullableSimpleCommit {
+    /// Information about the Git author
+    pub author: Option<NullableSimpleCommitAuthor>,
+
+    /// Information about the Git committer
+    pub committer: Option<NullableSimpleCommitCommitter>,
+
+    /// SHA for the commit
+    pub id: Oid,
+
+    /// Message describing the purpose of the commit
+    pub message: String,
+
+    /// Timestamp of the commit
+    pub timestamp: DateTime,
+
+    /// SHA for the commit's tree
+    pub tree_id: Oid,
+}
+
+/// Information about the Git author
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NullableSimpleCommitAuthor {
+    /// Git email address of the commit's author
+    pub email: String,
+
+    /// Name of the commit's author
+    pub name: String,
+}
+
+/// Information about the Git committer
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NullableSimpleCommitCommitter {
+    /// Git email address of the commit's committer
+    pub email: String,
+
+    /// Name of the commit's committer
+    pub name: String,
+}
+
+/// Pull Request Minimal
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestMinimal {
+    pub base: PullRequestMinimalBase,
+    pub head: PullRequestMinimalHead,
+    pub id: u64,
+    pub number: IssueNumber,
+    pub url: String,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestMinimalBase {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub repo: PullRequestMinimalBaseRepo,
+    pub sha: Oid,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestMinimalBaseRepo {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestMinimalHead {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub repo: PullRequestMinimalHeadRepo,
+    pub sha: Oid,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestMinimalHeadRepo {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+}
+
+/// Reaction Rollup
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReactionRollup {
+    #[serde(rename = "+1")]
+    pub plus_one: u64,
+    #[serde(rename = "-1")]
+    pub minus_one: u64,
+    pub confused: u64,
+    pub eyes: u64,
+    pub heart: u64,
+    pub hooray: u64,
+    pub laugh: u64,
+    pub rocket: u64,
+    pub total_count: u64,
+    pub url: String,
+}
+
+/// A workflow referenced/reused by the initial caller workflow
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReferencedWorkflow {
+    pub path: String,
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    pub sha: Oid,
+}
+
+/// A release.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Release {
+    pub assets: Vec<ReleaseAsset>,
+
+    pub assets_url: String,
+
+    pub author: User,
+
+    pub body: Option<String>,
+
+    pub body_html: Option<String>,
+
+    pub body_text: Option<String>,
+
+    pub created_at: DateTime,
+
+    /// The URL of the release discussion.
+    pub discussion_url: Option<String>,
+
+    /// true to create a draft (unpublished) release, false to create a
+    /// published one.
+    pub draft: bool,
+
+    pub html_url: String,
+
+    pub id: u64,
+
+    pub mentions_count: Option<u64>,
+
+    pub name: Option<String>,
+
+    pub node_id: String,
+
+    /// Whether to identify the release as a prerelease or a full release.
+    pub prerelease: bool,
+
+    pub published_at: Option<DateTime>,
+
+    pub reactions: Option<ReactionRollup>,
+
+    /// The name of the tag.
+    pub tag_name: String,
+
+    pub tarball_url: Option<String>,
+
+    /// Specifies the commitish value that determines where the Git tag is
+    /// created from.
+    pub target_commitish: String,
+
+    pub upload_url: String,
+
+    pub url: String,
+
+    pub zipball_url: Option<String>,
+}
+
+/// Data related to a release.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReleaseAsset {
+    pub browser_download_url: String,
+
+    pub content_type: String,
+
+    pub created_at: DateTime,
+
+    pub download_count: u64,
+
+    pub id: u64,
+
+    pub label: Option<String>,
+
+    /// The file name of the asset.
+    pub name: String,
+
+    pub node_id: String,
+
+    pub size: u64,
+
+    /// State of the release asset.
+    pub state: ReleaseAssetState,
+
+    pub updated_at: DateTime,
+
+    pub uploader: Option<User>,
+
+    pub url: String,
+}
+
+/// State of the release asset.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseAssetState {
+    Uploaded,
+    Open,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SecurityAndAnalysis {
+    pub advanced_security: Option<SecurityAndAnalysisAdvancedSecurity>,
+
+    /// Enable or disable Dependabot security updates for the repository.
+    pub dependabot_security_updates:
+        Option<SecurityAndAnalysisDependabotSecurityUpdates>,
+
+    pub secret_scanning: Option<SecurityAndAnalysisSecretScanning>,
+
+    pub secret_scanning_push_protection:
+        Option<SecurityAndAnalysisSecretScanningPushProtection>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SecurityAndAnalysisAdvancedSecurity {
+    pub status: Option<SecurityAndAnalysisAdvancedSecurityStatus>,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityAndAnalysisAdvancedSecurityStatus {
+    Enabled,
+    Disabled,
+}
+
+/// Enable or disable Dependabot security updates for the repository.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SecurityAndAnalysisDependabotSecurityUpdates {
+    /// The enablement status of Dependabot security updates for the
+    /// repository.
+    pub status: Option<SecurityAndAnalysisDependabotSecurityUpdatesStatus>,
+}
+
+/// The enablement status of Dependabot security updates for the repository.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityAndAnalysisDependabotSecurityUpdatesStatus {
+    Enabled,
+    Disabled,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SecurityAndAnalysisSecretScanning {
+    pub status: Option<SecurityAndAnalysisSecretScanningStatus>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SecurityAndAnalysisSecretScanningPushProtection {
+    pub status: Option<SecurityAndAnalysisSecretScanningPushProtectionStatus>,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityAndAnalysisSecretScanningPushProtectionStatus {
+    Enabled,
+    Disabled,
+}
+
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityAndAnalysisSecretScanningStatus {
+    Enabled,
+    Disabled,
+}
+
+/// An invocation of a workflow
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkflowRun {
+    pub actor: Option<User>,
+
+    /// The URL to the artifacts for the workflow run.
+    pub artifacts_url: String,
+
+    /// The URL to cancel the workflow run.
+    pub cancel_url: String,
+
+    /// The ID of the associated check suite.
+    pub check_suite_id: Option<CheckSuiteId>,
+
+    /// The node ID of the associated check suite.
+    pub check_suite_node_id: Option<String>,
+
+    /// The URL to the associated check suite.
+    pub check_suite_url: String,
+
+    pub conclusion: Option<Conclusion>,
+
+    pub created_at: DateTime,
+
+    /// The event-specific title associated with the run or the run-name if
+    /// set, or the value of `run-name` if it is set in the workflow.
+    pub display_title: String,
+
+    pub event: String,
+
+    pub head_branch: Option<String>,
+
+    pub head_commit: Option<NullableSimpleCommit>,
+
+    pub head_repository: MinimalRepository,
+
+    pub head_repository_id: Option<RepoId>,
+
+    /// The SHA of the head commit that points to the version of the workflow
+    /// being run.
+    pub head_sha: Oid,
+
+    pub html_url: String,
+
+    /// The ID of the workflow run.
+    pub id: u64,
+
+    /// The URL to the jobs for the workflow run.
+    pub jobs_url: String,
+
+    /// The URL to download the logs for the workflow run.
+    pub logs_url: String,
+
+    /// The name of the workflow run.
+    pub name: Option<String>,
+
+    pub node_id: String,
+
+    /// The full path of the workflow
+    pub path: String,
+
+    /// The URL to the previous attempted run of this workflow, if one exists.
+    pub previous_attempt_url: Option<String>,
+
+    /// Pull requests that are open with a `head_sha` or `head_branch` that
+    /// matches the workflow run. The returned pull requests do not necessarily
+    /// indicate pull requests that triggered the run.
+    pub pull_requests: Option<Vec<PullRequestMinimal>>,
+
+    pub referenced_workflows: Option<Vec<ReferencedWorkflow>>,
+
+    pub repository: MinimalRepository,
+
+    /// The URL to rerun the workflow run.
+    pub rerun_url: String,
+
+    /// Attempt number of the run, 1 for first attempt and higher if the
+    /// workflow was re-run.
+    pub run_attempt: Option<u64>,
+
+    /// The auto incrementing run number for the workflow run.
+    pub run_number: u64,
+
+    /// The start time of the latest run. Resets on re-run.
+    pub run_started_at: Option<DateTime>,
+
+    pub status: Option<CheckRunStatus>,
+
+    pub triggering_actor: Option<User>,
+
+    pub updated_at: DateTime,
+
+    /// The URL to the workflow run.
+    pub url: String,
+
+    /// The ID of the parent workflow.
+    pub workflow_id: u64,
+
+    /// The URL to the workflow.
+    pub workflow_url: String,
+}
@@ -0,0 +1,163 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Strongly typed IDs.
+//!
+//! GitHub identifies most objects with an integer. Each kind of object gets
+//! its own type here so that, for example, a repository ID can't be passed
+//! where an installation ID is expected. All of them serialize as the bare
+//! integer.
+
+use derive_more::{Display, From, Into};
+use serde::{Deserialize, Serialize};
+
+macro_rules! id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Serialize,
+            Deserialize,
+            From,
+            Into,
+            Display,
+            Debug,
+            Copy,
+            Clone,
+            Eq,
+            PartialEq,
+            Ord,
+            PartialOrd,
+            Hash,
+        )]
+        #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+        #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+        #[serde(transparent)]
+        pub struct $name(pub u64);
+    };
+}
+
+id! {
+    /// The ID of a GitHub App.
+    AppId
+}
+
+id! {
+    /// The ID of a check run.
+    CheckRunId
+}
+
+id! {
+    /// The ID of a check suite.
+    CheckSuiteId
+}
+
+id! {
+    /// The ID of a commit or issue comment.
+    CommentId
+}
+
+id! {
+    /// The ID of a deploy key.
+    DeployKeyId
+}
+
+id! {
+    /// The ID of a webhook.
+    HookId
+}
+
+id! {
+    /// The ID of an app installation.
+    InstallationId
+}
+
+id! {
+    /// The ID of an issue. This is unique across all repositories. See
+    /// `IssueNumber` for the number shown in the UI.
+    IssueId
+}
+
+id! {
+    /// The number of an issue or pull request within its repository. Issues
+    /// and pull requests share the same sequence of numbers.
+    IssueNumber
+}
+
+id! {
+    /// The ID of a milestone.
+    MilestoneId
+}
+
+id! {
+    /// The number of a milestone within its repository.
+    MilestoneNumber
+}
+
+id! {
+    /// The ID of a package.
+    PackageId
+}
+
+id! {
+    /// The ID of a file in a package version.
+    PackageFileId
+}
+
+id! {
+    /// The ID of a package version.
+    PackageVersionId
+}
+
+id! {
+    /// The ID of a pull request. This is unique across all repositories and
+    /// is different from the ID of the corresponding issue. See
+    /// `IssueNumber` for the number shown in the UI.
+    PullRequestId
+}
+
+id! {
+    /// The ID of a repository.
+    RepoId
+}
+
+id! {
+    /// The ID of a pull request review.
+    ReviewId
+}
+
+id! {
+    /// The ID of a pull request review comment.
+    ReviewCommentId
+}
+
+id! {
+    /// The ID of a repository ruleset.
+    RulesetId
+}
+
+id! {
+    /// The ID of a team.
+    TeamId
+}
+
+id! {
+    /// The ID of a user or organization. Both share the same sequence of IDs.
+    UserId
+}
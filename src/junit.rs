@@ -0,0 +1,957 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Conversion of JUnit XML test reports into check run output.
+//!
+//! There is no formal JUnit XML schema, so this accepts the common dialect
+//! produced by JUnit, pytest, Jest, Go's `go-junit-report`, `cargo-nextest`
+//! and others. Both a `<testsuites>` and a lone `<testsuite>` root element are
+//! supported.
+//!
+//! This module requires the `junit` feature.
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use std::error;
+use std::fmt;
+use std::path::{Component, Path};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::check_run_request::truncate_annotation_text;
+use crate::markdown::{self, Alignment, Markdown, Table};
+use crate::{
+    Annotation, AnnotationLevel, Conclusion, Output, MAX_ANNOTATION_TITLE_LEN,
+    MAX_OUTPUT_TEXT_LEN,
+};
+
+/// An error that occurred while parsing a JUnit XML report.
+#[derive(Debug)]
+pub enum ParseReportError {
+    /// The report is not well-formed XML.
+    Xml(quick_xml::Error),
+
+    /// The root element is not `<testsuites>` or `<testsuite>`.
+    InvalidRoot(String),
+
+    /// The document has no root element.
+    MissingRoot,
+
+    /// Test suites are nested more than `MAX_SUITE_DEPTH` levels deep.
+    TooDeep,
+}
+
+/// The maximum nesting depth of `<testsuite>` elements.
+pub const MAX_SUITE_DEPTH: usize = 64;
+
+impl fmt::Display for ParseReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseReportError::Xml(err) => write!(f, "invalid XML: {}", err),
+            ParseReportError::InvalidRoot(name) => {
+                write!(f, "unexpected root element <{}>", name)
+            }
+            ParseReportError::MissingRoot => {
+                write!(f, "missing root element")
+            }
+            ParseReportError::TooDeep => write!(
+                f,
+                "test suites are nested more than {} levels deep",
+                MAX_SUITE_DEPTH
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseReportError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseReportError::Xml(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for ParseReportError
+where
+    T: Into<quick_xml::Error>,
+{
+    fn from(err: T) -> Self {
+        ParseReportError::Xml(err.into())
+    }
+}
+
+/// Details about a failed test.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TestFailure {
+    /// The `message` attribute, usually the assertion message.
+    pub message: Option<String>,
+
+    /// The `type` attribute, usually the exception type.
+    pub failure_type: Option<String>,
+
+    /// The body of the element, usually a stack trace.
+    pub text: String,
+}
+
+impl TestFailure {
+    /// Returns a one line description of the failure.
+    fn headline(&self) -> String {
+        let message = self
+            .message
+            .as_ref()
+            .map(|m| m.trim())
+            .filter(|m| !m.is_empty())
+            .or_else(|| {
+                self.text.lines().map(str::trim).find(|l| !l.is_empty())
+            });
+
+        match (&self.failure_type, message) {
+            (_, Some(message)) => message.to_string(),
+            (Some(t), None) => t.clone(),
+            (None, None) => "Test failed".to_string(),
+        }
+    }
+}
+
+/// The outcome of a single test case.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TestOutcome {
+    Passed,
+
+    /// An assertion in the test failed (`<failure>`).
+    Failed(TestFailure),
+
+    /// The test raised an unexpected error (`<error>`).
+    Errored(TestFailure),
+
+    /// The test was skipped, with an optional reason (`<skipped>`).
+    Skipped(Option<String>),
+}
+
+/// A single test case.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TestCase {
+    pub name: String,
+
+    /// The class or module the test belongs to.
+    pub classname: Option<String>,
+
+    /// The file containing the test, if the report includes it.
+    pub file: Option<String>,
+
+    /// The line of the test in `file`, if the report includes it.
+    pub line: Option<u32>,
+
+    /// How long the test took to run.
+    pub time: Option<Duration>,
+
+    pub outcome: TestOutcome,
+}
+
+impl TestCase {
+    /// Returns the fully qualified name of the test.
+    pub fn full_name(&self) -> String {
+        match &self.classname {
+            Some(classname) if !classname.is_empty() => {
+                format!("{}.{}", classname, self.name)
+            }
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Returns the failure details of the test, if it failed.
+    pub fn failure(&self) -> Option<&TestFailure> {
+        match &self.outcome {
+            TestOutcome::Failed(f) | TestOutcome::Errored(f) => Some(f),
+            _ => None,
+        }
+    }
+}
+
+/// Test counts for a suite or a whole report.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub errored: usize,
+    pub skipped: usize,
+}
+
+impl TestCounts {
+    /// The total number of tests.
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.errored + self.skipped
+    }
+
+    /// The suggested conclusion of a check run with these counts.
+    ///
+    /// Any failure or error results in `Failure`. If every test was skipped,
+    /// the conclusion is `Skipped`, and if there are no tests at all, it is
+    /// `Neutral`.
+    pub fn conclusion(&self) -> Conclusion {
+        if self.failed > 0 || self.errored > 0 {
+            Conclusion::Failure
+        } else if self.total() == 0 {
+            Conclusion::Neutral
+        } else if self.passed == 0 {
+            Conclusion::Skipped
+        } else {
+            Conclusion::Success
+        }
+    }
+
+    fn add(&mut self, outcome: &TestOutcome) {
+        match outcome {
+            TestOutcome::Passed => self.passed += 1,
+            TestOutcome::Failed(_) => self.failed += 1,
+            TestOutcome::Errored(_) => self.errored += 1,
+            TestOutcome::Skipped(_) => self.skipped += 1,
+        }
+    }
+
+    /// Returns a short description, such as "10 passed, 1 failed".
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+
+        if self.passed > 0 || self.total() == 0 {
+            parts.push(format!("{} passed", self.passed));
+        }
+        if self.failed > 0 {
+            parts.push(format!("{} failed", self.failed));
+        }
+        if self.errored > 0 {
+            parts.push(format!("{} errored", self.errored));
+        }
+        if self.skipped > 0 {
+            parts.push(format!("{} skipped", self.skipped));
+        }
+
+        parts.join(", ")
+    }
+}
+
+impl std::ops::AddAssign for TestCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.errored += other.errored;
+        self.skipped += other.skipped;
+    }
+}
+
+/// A suite of test cases (`<testsuite>`).
+///
+/// Nested suites are flattened into the report.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TestSuite {
+    pub name: String,
+
+    /// The file containing the suite, if the report includes it.
+    pub file: Option<String>,
+
+    /// How long the suite took to run, as reported by the test runner.
+    pub time: Option<Duration>,
+
+    pub tests: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// Counts the test outcomes in this suite.
+    pub fn counts(&self) -> TestCounts {
+        let mut counts = TestCounts::default();
+
+        for test in &self.tests {
+            counts.add(&test.outcome);
+        }
+
+        counts
+    }
+
+    /// Returns the duration of the suite. If the report doesn't include it,
+    /// this is the sum of the test durations.
+    pub fn duration(&self) -> Duration {
+        self.time
+            .unwrap_or_else(|| self.tests.iter().filter_map(|t| t.time).sum())
+    }
+
+    /// The suggested conclusion of a check run for this suite.
+    pub fn conclusion(&self) -> Conclusion {
+        self.counts().conclusion()
+    }
+
+    /// Converts the suite into check run output.
+    ///
+    /// See `TestReport::to_output`.
+    pub fn to_output(&self, workspace_root: &Path) -> Output {
+        to_output(std::slice::from_ref(self), workspace_root)
+    }
+}
+
+/// A parsed JUnit XML report.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TestReport {
+    pub suites: Vec<TestSuite>,
+}
+
+impl TestReport {
+    /// Parses a JUnit XML report.
+    pub fn parse(xml: &str) -> Result<Self, ParseReportError> {
+        Parser::new(xml).parse()
+    }
+
+    /// Counts the test outcomes in all suites.
+    pub fn counts(&self) -> TestCounts {
+        let mut counts = TestCounts::default();
+
+        for suite in &self.suites {
+            counts += suite.counts();
+        }
+
+        counts
+    }
+
+    /// The suggested conclusion of a check run for this report.
+    pub fn conclusion(&self) -> Conclusion {
+        self.counts().conclusion()
+    }
+
+    /// Converts the report into check run output.
+    ///
+    /// The summary contains a table of test counts and durations for each
+    /// suite, and the text contains the details of each failure. Failed tests
+    /// become annotations if the report includes the file they are in. Paths
+    /// are made relative to `workspace_root`.
+    ///
+    /// The annotations are not limited to `MAX_ANNOTATIONS`; use
+    /// `Output::into_batches` to split them up.
+    pub fn to_output(&self, workspace_root: &Path) -> Output {
+        to_output(&self.suites, workspace_root)
+    }
+}
+
+impl FromStr for TestReport {
+    type Err = ParseReportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TestReport::parse(s)
+    }
+}
+
+/// Returns the path of `file` relative to the workspace root, using `/` as
+/// the separator. Returns `None` if the file is outside of the workspace.
+fn relativize(file: &str, workspace_root: &Path) -> Option<String> {
+    let path = Path::new(file);
+
+    let path = if path.is_absolute() {
+        path.strip_prefix(workspace_root).ok()?
+    } else {
+        path
+    };
+
+    let components = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .map(|c| match c {
+            Component::Normal(c) => c.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    if components.is_empty() {
+        return None;
+    }
+
+    Some(components.join("/"))
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.2}s", d.as_secs_f64())
+}
+
+fn to_output(suites: &[TestSuite], workspace_root: &Path) -> Output {
+    let mut counts = TestCounts::default();
+    let mut duration = Duration::default();
+
+    let mut table = Table::new([
+        "Suite", "Passed", "Failed", "Errored", "Skipped", "Duration",
+    ]);
+    for column in 1..6 {
+        table = table.align(column, Alignment::Right);
+    }
+
+    for suite in suites {
+        let c = suite.counts();
+        let d = suite.duration();
+
+        table.row(vec![
+            suite.name.clone(),
+            c.passed.to_string(),
+            c.failed.to_string(),
+            c.errored.to_string(),
+            c.skipped.to_string(),
+            format_duration(d),
+        ]);
+
+        counts += c;
+        duration += d;
+    }
+
+    if suites.len() > 1 {
+        table.raw_row(vec![
+            "**Total**".to_string(),
+            format!("**{}**", counts.passed),
+            format!("**{}**", counts.failed),
+            format!("**{}**", counts.errored),
+            format!("**{}**", counts.skipped),
+            format!("**{}**", format_duration(duration)),
+        ]);
+    }
+
+    let mut summary = Markdown::new();
+    summary.table(&table);
+
+    let mut annotations = Vec::new();
+    let mut text = Markdown::new();
+    let mut omitted = 0;
+
+    for suite in suites {
+        for test in &suite.tests {
+            let failure = match test.failure() {
+                Some(failure) => failure,
+                None => continue,
+            };
+
+            let name = test.full_name();
+
+            let path = test
+                .file
+                .as_ref()
+                .or(suite.file.as_ref())
+                .and_then(|f| relativize(f, workspace_root));
+
+            if let Some(path) = path {
+                let line = test.line.unwrap_or(1).max(1);
+                let details = failure.text.trim();
+
+                annotations.push(Annotation {
+                    path,
+                    start_line: line,
+                    end_line: line,
+                    start_column: None,
+                    end_column: None,
+                    annotation_level: AnnotationLevel::Failure,
+                    message: truncate_annotation_text(failure.headline()),
+                    title: Some(
+                        name.chars().take(MAX_ANNOTATION_TITLE_LEN).collect(),
+                    ),
+                    raw_details: if details.is_empty() {
+                        None
+                    } else {
+                        Some(truncate_annotation_text(details.to_string()))
+                    },
+                });
+            }
+
+            let mut section = Markdown::new();
+            section.raw(&format!("### {}", markdown::code(&name)));
+            if let Some(message) = &failure.message {
+                section.paragraph(message.trim());
+            }
+            if !failure.text.trim().is_empty() {
+                section.code_block("", failure.text.trim_matches('\n'));
+            }
+
+            // Only include complete failures, leaving room for the notice
+            // about omitted ones.
+            if omitted > 0
+                || text.len() + section.len() + 2 > MAX_OUTPUT_TEXT_LEN - 100
+            {
+                omitted += 1;
+            } else {
+                text.raw(section.as_str());
+            }
+        }
+    }
+
+    if omitted > 0 {
+        text.raw(&format!("*{} more failures were omitted.*", omitted));
+    }
+
+    Output {
+        title: counts.describe(),
+        summary: summary.finish(),
+        text: if text.is_empty() {
+            None
+        } else {
+            Some(text.finish())
+        },
+        annotations: Some(annotations),
+        images: None,
+    }
+}
+
+/// Parses a duration in (possibly fractional) seconds.
+///
+/// Some runners format the time according to the locale, so a lone `,` is
+/// accepted as the decimal separator. Anything with both `,` and `.` (i.e.,
+/// with a thousands separator) is ambiguous and rejected.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+
+    let secs: f64 = if s.contains('.') {
+        if s.contains(',') {
+            return None;
+        }
+        s.parse().ok()?
+    } else {
+        s.replacen(',', ".", 1).parse().ok()?
+    };
+
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// An element whose attributes have been read.
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn string(&self, name: &str) -> Option<String> {
+        self.attr(name).map(String::from)
+    }
+}
+
+/// A streaming parser for JUnit XML reports.
+struct Parser<'a> {
+    reader: Reader<&'a [u8]>,
+    suites: Vec<TestSuite>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(xml: &'a str) -> Self {
+        Parser {
+            reader: Reader::from_str(xml),
+            suites: Vec::new(),
+        }
+    }
+
+    fn element(e: &BytesStart) -> Result<Element, ParseReportError> {
+        let name =
+            String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+
+        let mut attributes = Vec::new();
+        for attr in e.attributes() {
+            let attr = attr?;
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref())
+                .into_owned();
+            attributes.push((key, attr.unescape_value()?.into_owned()));
+        }
+
+        Ok(Element { name, attributes })
+    }
+
+    /// Reads the next element, skipping over everything else. Returns the
+    /// element and whether or not it is empty (i.e., `<foo/>`). Returns
+    /// `None` at the end of the parent element or document.
+    fn next_element(
+        &mut self,
+    ) -> Result<Option<(Element, bool)>, ParseReportError> {
+        loop {
+            match self.reader.read_event()? {
+                Event::Start(e) => {
+                    return Ok(Some((Self::element(&e)?, false)))
+                }
+                Event::Empty(e) => return Ok(Some((Self::element(&e)?, true))),
+                Event::End(_) | Event::Eof => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the text content of the current element, including the content
+    /// of any child elements.
+    fn read_text(&mut self) -> Result<String, ParseReportError> {
+        let mut text = String::new();
+        let mut depth = 0usize;
+
+        loop {
+            match self.reader.read_event()? {
+                Event::Text(t) => text.push_str(&t.unescape()?),
+                Event::CData(t) => {
+                    text.push_str(&String::from_utf8_lossy(&t.into_inner()))
+                }
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth > 0 => depth -= 1,
+                Event::End(_) | Event::Eof => return Ok(text),
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips over the content of the current element.
+    fn skip(&mut self) -> Result<(), ParseReportError> {
+        self.read_text().map(|_| ())
+    }
+
+    fn parse(mut self) -> Result<TestReport, ParseReportError> {
+        let (root, empty) = match self.next_element()? {
+            Some(root) => root,
+            None => return Err(ParseReportError::MissingRoot),
+        };
+
+        match root.name.as_str() {
+            "testsuites" => {
+                if !empty {
+                    self.parse_suites(None)?;
+                }
+            }
+            "testsuite" => self.parse_suite(&root, empty, None, 0)?,
+            _ => return Err(ParseReportError::InvalidRoot(root.name)),
+        }
+
+        Ok(TestReport {
+            suites: self.suites,
+        })
+    }
+
+    /// Parses the children of a `<testsuites>` element.
+    fn parse_suites(
+        &mut self,
+        parent: Option<&str>,
+    ) -> Result<(), ParseReportError> {
+        while let Some((element, empty)) = self.next_element()? {
+            if element.name == "testsuite" {
+                self.parse_suite(&element, empty, parent, 0)?;
+            } else if !empty {
+                self.skip()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses a `<testsuite>` element. Nested suites are added to the report
+    /// after their parent, with their names prefixed by the parent's name.
+    fn parse_suite(
+        &mut self,
+        element: &Element,
+        empty: bool,
+        parent: Option<&str>,
+        depth: usize,
+    ) -> Result<(), ParseReportError> {
+        if depth >= MAX_SUITE_DEPTH {
+            return Err(ParseReportError::TooDeep);
+        }
+
+        let name = element.string("name").unwrap_or_default();
+        let name = match parent {
+            Some(parent) if !parent.is_empty() => {
+                format!("{}.{}", parent, name)
+            }
+            _ => name,
+        };
+
+        let index = self.suites.len();
+        self.suites.push(TestSuite {
+            name: name.clone(),
+            file: element
+                .string("file")
+                .or_else(|| element.string("filepath")),
+            time: element.attr("time").and_then(parse_duration),
+            tests: Vec::new(),
+        });
+
+        if empty {
+            return Ok(());
+        }
+
+        while let Some((child, child_empty)) = self.next_element()? {
+            match child.name.as_str() {
+                "testcase" => {
+                    let test = self.parse_case(&child, child_empty)?;
+                    self.suites[index].tests.push(test);
+                }
+                "testsuite" => self.parse_suite(
+                    &child,
+                    child_empty,
+                    Some(&name),
+                    depth + 1,
+                )?,
+                _ if !child_empty => self.skip()?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses a `<testcase>` element.
+    fn parse_case(
+        &mut self,
+        element: &Element,
+        empty: bool,
+    ) -> Result<TestCase, ParseReportError> {
+        let mut outcome = TestOutcome::Passed;
+
+        if !empty {
+            while let Some((child, child_empty)) = self.next_element()? {
+                let text = if child_empty {
+                    String::new()
+                } else {
+                    self.read_text()?
+                };
+
+                let failure = || TestFailure {
+                    message: child.string("message"),
+                    failure_type: child.string("type"),
+                    text: text.clone(),
+                };
+
+                // The first failure or error takes precedence over anything
+                // else. A skip only counts if the test didn't fail.
+                match (child.name.as_str(), &outcome) {
+                    ("failure", TestOutcome::Passed)
+                    | ("failure", TestOutcome::Skipped(_)) => {
+                        outcome = TestOutcome::Failed(failure())
+                    }
+                    ("error", TestOutcome::Passed)
+                    | ("error", TestOutcome::Skipped(_)) => {
+                        outcome = TestOutcome::Errored(failure())
+                    }
+                    ("skipped", TestOutcome::Passed) => {
+                        let reason = child
+                            .string("message")
+                            .or_else(|| Some(text.trim().to_string()))
+                            .filter(|r| !r.is_empty());
+                        outcome = TestOutcome::Skipped(reason);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(TestCase {
+            name: element.string("name").unwrap_or_default(),
+            classname: element.string("classname"),
+            file: element
+                .string("file")
+                .or_else(|| element.string("filepath")),
+            line: element.attr("line").and_then(|l| l.trim().parse().ok()),
+            time: element.attr("time").and_then(parse_duration),
+            outcome,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MAX_ANNOTATION_MESSAGE_SIZE;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("1.5"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration(" 2 "), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("0.001"), Some(Duration::from_millis(1)));
+
+        // A locale-specific decimal separator.
+        assert_eq!(parse_duration("1,5"), Some(Duration::from_millis(1500)));
+
+        // Thousands separators are ambiguous.
+        assert_eq!(parse_duration("1,234.5"), None);
+        assert_eq!(parse_duration("1.234,5"), None);
+        assert_eq!(parse_duration("1,234,5"), None);
+
+        assert_eq!(parse_duration("-1"), None);
+        assert_eq!(parse_duration("NaN"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn outcomes() {
+        let report = TestReport::parse(
+            r#"<testsuite name="suite" time="1,25">
+                <testcase name="passed" classname="a.b" time="0.5"/>
+                <testcase name="failed">
+                    <skipped/>
+                    <failure message="assertion failed" type="Assert">
+                        trace
+                    </failure>
+                    <error message="ignored"/>
+                </testcase>
+                <testcase name="errored"><error type="Panic"/></testcase>
+                <testcase name="skipped"><skipped>not today</skipped></testcase>
+            </testsuite>"#,
+        )
+        .unwrap();
+
+        assert_eq!(report.suites.len(), 1);
+        let suite = &report.suites[0];
+        assert_eq!(suite.time, Some(Duration::from_millis(1250)));
+
+        let tests = &suite.tests;
+        assert_eq!(tests[0].full_name(), "a.b.passed");
+        assert_eq!(tests[0].outcome, TestOutcome::Passed);
+        assert_eq!(tests[0].time, Some(Duration::from_millis(500)));
+
+        let failure = tests[1].failure().unwrap();
+        assert!(matches!(tests[1].outcome, TestOutcome::Failed(_)));
+        assert_eq!(failure.message.as_deref(), Some("assertion failed"));
+        assert_eq!(failure.failure_type.as_deref(), Some("Assert"));
+        assert_eq!(failure.text.trim(), "trace");
+
+        assert!(matches!(tests[2].outcome, TestOutcome::Errored(_)));
+        assert_eq!(tests[2].failure().unwrap().headline(), "Panic");
+
+        assert_eq!(
+            tests[3].outcome,
+            TestOutcome::Skipped(Some("not today".to_string()))
+        );
+
+        assert_eq!(
+            report.counts(),
+            TestCounts {
+                passed: 1,
+                failed: 1,
+                errored: 1,
+                skipped: 1,
+            }
+        );
+        assert_eq!(report.conclusion(), Conclusion::Failure);
+    }
+
+    #[test]
+    fn nested_suites() {
+        let report = TestReport::parse(
+            r#"<testsuites>
+                <testsuite name="a">
+                    <testcase name="1"/>
+                    <testsuite name="b">
+                        <testsuite name="c"><testcase name="2"/></testsuite>
+                    </testsuite>
+                    <testcase name="3"/>
+                </testsuite>
+                <testsuite name="d"/>
+            </testsuites>"#,
+        )
+        .unwrap();
+
+        let names: Vec<_> =
+            report.suites.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "a.b", "a.b.c", "d"]);
+
+        assert_eq!(report.suites[0].tests.len(), 2);
+        assert_eq!(report.suites[2].tests[0].name, "2");
+    }
+
+    #[test]
+    fn too_deep() {
+        let xml = |depth| {
+            format!(
+                "{}{}",
+                "<testsuite>".repeat(depth),
+                "</testsuite>".repeat(depth)
+            )
+        };
+
+        assert!(TestReport::parse(&xml(MAX_SUITE_DEPTH)).is_ok());
+        assert!(matches!(
+            TestReport::parse(&xml(MAX_SUITE_DEPTH + 1)),
+            Err(ParseReportError::TooDeep)
+        ));
+        assert!(matches!(
+            TestReport::parse(&xml(100_000)),
+            Err(ParseReportError::TooDeep)
+        ));
+    }
+
+    #[test]
+    fn invalid_root() {
+        assert!(matches!(
+            TestReport::parse("<html/>"),
+            Err(ParseReportError::InvalidRoot(name)) if name == "html"
+        ));
+        assert!(matches!(
+            TestReport::parse(""),
+            Err(ParseReportError::MissingRoot)
+        ));
+    }
+
+    #[test]
+    fn annotations() {
+        let report = TestReport::parse(
+            r#"<testsuite name="s" file="/work/tests/a.py">
+                <testcase name="t" line="7"><failure message="boom"/></testcase>
+                <testcase name="u" file="/elsewhere/b.py"><failure/></testcase>
+            </testsuite>"#,
+        )
+        .unwrap();
+
+        let output = report.to_output(Path::new("/work"));
+        let annotations = output.annotations.unwrap();
+
+        // The second failure is outside the workspace.
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].path, "tests/a.py");
+        assert_eq!(annotations[0].start_line, 7);
+        assert_eq!(annotations[0].message, "boom");
+        assert_eq!(annotations[0].title.as_deref(), Some("t"));
+
+        assert_eq!(output.title, "2 failed");
+        assert!(output.text.unwrap().contains("### `t`"));
+    }
+
+    #[test]
+    fn large_failures_are_truncated() {
+        let name = "n".repeat(300);
+        let text = format!("a{}", "é".repeat(40_000));
+        let report = TestReport::parse(&format!(
+            r#"<testsuite name="s" file="a.py">
+                <testcase name="{}"><failure>{}</failure></testcase>
+            </testsuite>"#,
+            name, text
+        ))
+        .unwrap();
+
+        let output = report.to_output(Path::new("/work"));
+        let annotation = &output.annotations.unwrap()[0];
+
+        assert_eq!(
+            annotation.title.as_deref().unwrap().len(),
+            MAX_ANNOTATION_TITLE_LEN
+        );
+        assert_eq!(annotation.message.len(), MAX_ANNOTATION_MESSAGE_SIZE - 1);
+
+        let details = annotation.raw_details.as_deref().unwrap();
+        assert_eq!(details.len(), MAX_ANNOTATION_MESSAGE_SIZE - 1);
+        assert!(details.ends_with('é'));
+    }
+}
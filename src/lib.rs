@@ -19,19 +19,52 @@
 // SOFTWARE.
 
 mod app;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod cargo;
+mod check_run_request;
 mod checks;
 mod datetime;
+mod diff;
+mod dispatch;
+#[cfg(feature = "strict")]
+pub mod drift;
 mod events;
+#[cfg(feature = "test-util")]
+mod fake;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+mod generated;
+mod id;
+#[cfg(feature = "junit")]
+pub mod junit;
+pub mod markdown;
 mod oid;
+mod package;
 mod previews;
 mod repo;
+mod review;
+mod ruleset;
+pub mod sarif;
+#[cfg(feature = "schemars")]
+pub mod schema;
 mod user;
 
 pub use app::*;
+pub use check_run_request::*;
 pub use checks::*;
 pub use datetime::*;
+pub use diff::*;
+pub use dispatch::*;
 pub use events::*;
+#[cfg(feature = "test-util")]
+pub use fake::*;
+pub use generated::*;
+pub use id::*;
 pub use oid::*;
+pub use package::*;
 pub use previews::*;
 pub use repo::*;
+pub use review::*;
+pub use ruleset::*;
 pub use user::*;
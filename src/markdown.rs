@@ -0,0 +1,575 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! A builder for GitHub-flavored markdown reports.
+//!
+//! This is intended for building `Output::summary`, `Output::text` and comment
+//! bodies. All user-supplied text is escaped unless it is explicitly added as
+//! raw markdown, and the result can be truncated to fit within GitHub's size
+//! limits without leaving code fences or `<details>` sections unclosed.
+
+use std::fmt;
+
+use crate::MAX_OUTPUT_TEXT_LEN;
+
+/// The notice appended to truncated markdown.
+const TRUNCATED_NOTICE: &str = "\n*Output truncated.*\n";
+
+/// Escapes text so that it is rendered literally in markdown.
+///
+/// Characters that have meaning anywhere in a line are always escaped with a
+/// backslash, while characters that only have meaning at the start of a line
+/// (such as `#` or `1.`) are only escaped there. Newlines are preserved.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        escaped.push_str(&line[..indent]);
+        let line = &line[indent..];
+
+        // The end of a leading number, which would start an ordered list if
+        // followed by `.` or `)`.
+        let digits = line.len()
+            - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        for (j, c) in line.char_indices() {
+            let special = match c {
+                '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~'
+                | '&' => true,
+                '#' | '+' | '-' | '=' => j == 0,
+                '.' | ')' => j == digits && digits > 0,
+                _ => false,
+            };
+
+            if special {
+                escaped.push('\\');
+            }
+
+            escaped.push(c);
+        }
+    }
+
+    escaped
+}
+
+/// Escapes text for use inside a table cell. In addition to `escape`,
+/// newlines are replaced with `<br>` since a table row must be on one line.
+pub fn escape_cell(text: &str) -> String {
+    escape(text.trim_end_matches('\n'))
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Escapes text for use inside an HTML tag, where markdown isn't rendered and
+/// a backslash would be shown literally.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Returns the length of the longest run of `c` in `s`.
+fn longest_run(s: &str, c: char) -> usize {
+    s.split(|x| x != c).map(str::len).max().unwrap_or(0)
+}
+
+/// Formats text as inline code. The text is not escaped since it is rendered
+/// literally.
+pub fn code(text: &str) -> String {
+    let fence = "`".repeat(longest_run(text, '`') + 1);
+
+    // A space is needed to separate the fence from text that starts or ends
+    // with a backtick.
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{} {} {}", fence, text, fence)
+    } else {
+        format!("{}{}{}", fence, text, fence)
+    }
+}
+
+/// The alignment of a table column.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Alignment {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn delimiter(self) -> &'static str {
+        match self {
+            Alignment::None => "---",
+            Alignment::Left => ":---",
+            Alignment::Center => ":---:",
+            Alignment::Right => "---:",
+        }
+    }
+}
+
+/// A markdown table.
+///
+/// Cells are escaped when the table is rendered. Use `Table::raw_row` for
+/// cells that contain markdown.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Table {
+    headers: Vec<String>,
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Creates a table with the given column headers.
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let headers: Vec<_> = headers
+            .into_iter()
+            .map(|h| escape_cell(h.as_ref()))
+            .collect();
+
+        Table {
+            alignments: vec![Alignment::None; headers.len()],
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Sets the alignment of a column. Out of range columns are ignored.
+    pub fn align(mut self, column: usize, alignment: Alignment) -> Self {
+        if let Some(a) = self.alignments.get_mut(column) {
+            *a = alignment;
+        }
+
+        self
+    }
+
+    /// Adds a row of plain text cells. Rows with fewer cells than there are
+    /// columns are padded with empty cells, and extra cells are dropped.
+    pub fn row<I, S>(&mut self, cells: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.raw_row(cells.into_iter().map(|c| escape_cell(c.as_ref())))
+    }
+
+    /// Adds a row of cells that are already formatted as markdown. The cells
+    /// must not contain newlines or unescaped `|` characters.
+    pub fn raw_row<I, S>(&mut self, cells: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut row: Vec<String> = cells
+            .into_iter()
+            .take(self.headers.len())
+            .map(Into::into)
+            .collect();
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+        self
+    }
+
+    /// Returns the number of rows, excluding the header.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "| {} |", self.headers.join(" | "))?;
+
+        let delimiters: Vec<_> =
+            self.alignments.iter().map(|a| a.delimiter()).collect();
+        writeln!(f, "| {} |", delimiters.join(" | "))?;
+
+        for row in &self.rows {
+            writeln!(f, "| {} |", row.join(" | "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A builder for a markdown document.
+///
+/// Each block-level element is separated from the previous one by a blank
+/// line.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Markdown {
+    buf: String,
+}
+
+impl Markdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new block, separating it from the previous one.
+    fn block(&mut self) -> &mut String {
+        if !self.buf.is_empty() {
+            if !self.buf.ends_with('\n') {
+                self.buf.push('\n');
+            }
+
+            if !self.buf.ends_with("\n\n") {
+                self.buf.push('\n');
+            }
+        }
+
+        &mut self.buf
+    }
+
+    /// Adds a heading. The level is clamped to the range 1 to 6.
+    pub fn heading(&mut self, level: usize, text: &str) -> &mut Self {
+        let level = level.clamp(1, 6);
+        let line = format!("{} {}\n", "#".repeat(level), escape(text));
+        self.block().push_str(&line);
+        self
+    }
+
+    /// Adds a paragraph of plain text.
+    pub fn paragraph(&mut self, text: &str) -> &mut Self {
+        let text = escape(text);
+        self.raw(&text)
+    }
+
+    /// Adds a block of markdown as is.
+    pub fn raw(&mut self, markdown: &str) -> &mut Self {
+        let block = self.block();
+        block.push_str(markdown);
+        if !markdown.ends_with('\n') {
+            block.push('\n');
+        }
+        self
+    }
+
+    /// Adds a bulleted list of plain text items.
+    pub fn list<I, S>(&mut self, items: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut list = String::new();
+
+        for item in items {
+            // Indent continuation lines so they stay within the item.
+            let item = escape(item.as_ref()).replace('\n', "\n  ");
+            list.push_str("- ");
+            list.push_str(&item);
+            list.push('\n');
+        }
+
+        if !list.is_empty() {
+            self.block().push_str(&list);
+        }
+
+        self
+    }
+
+    /// Adds a fenced code block with an optional language for syntax
+    /// highlighting. The fence is made long enough that the code can't close
+    /// it.
+    pub fn code_block(&mut self, language: &str, code: &str) -> &mut Self {
+        let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+        let code = code.trim_end_matches('\n');
+        let block = format!("{}{}\n{}\n{}\n", fence, language, code, fence);
+        self.block().push_str(&block);
+        self
+    }
+
+    /// Adds a table.
+    pub fn table(&mut self, table: &Table) -> &mut Self {
+        let table = table.to_string();
+        self.block().push_str(&table);
+        self
+    }
+
+    /// Adds a collapsible section with a plain text summary. The contents
+    /// are built by the given function.
+    pub fn details<F>(&mut self, summary: &str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Markdown),
+    {
+        let mut inner = Markdown::new();
+        f(&mut inner);
+
+        // The blank line after `<summary>` is needed for the contents to be
+        // rendered as markdown.
+        let block = format!(
+            "<details>\n<summary>{}</summary>\n\n{}\n</details>\n",
+            escape_html(summary),
+            inner.buf
+        );
+        self.block().push_str(&block);
+        self
+    }
+
+    /// Returns the markdown built so far.
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Returns the number of characters in the markdown built so far.
+    pub fn len(&self) -> usize {
+        self.buf.chars().count()
+    }
+
+    /// Returns `true` if nothing has been added yet.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Returns the markdown, truncated to at most `MAX_OUTPUT_TEXT_LEN`
+    /// characters so that it fits in `Output::summary` or `Output::text`.
+    ///
+    /// See `truncate`.
+    pub fn finish(self) -> String {
+        truncate(&self.buf, MAX_OUTPUT_TEXT_LEN)
+    }
+
+    /// Returns the markdown without truncating it.
+    pub fn into_string(self) -> String {
+        self.buf
+    }
+}
+
+impl fmt::Display for Markdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+/// The constructs left open at the end of a prefix of a markdown document.
+#[derive(Default)]
+struct OpenBlocks {
+    /// The character and length of the open code fence, if any.
+    fence: Option<(char, usize)>,
+
+    /// The number of unclosed `<details>` tags.
+    details: usize,
+}
+
+impl OpenBlocks {
+    fn scan(markdown: &str) -> Self {
+        let mut open = OpenBlocks::default();
+
+        for line in markdown.lines() {
+            let trimmed = line.trim_start_matches(' ');
+
+            let fence = ['`', '~'].iter().find_map(|&c| {
+                let n = trimmed.len() - trimmed.trim_start_matches(c).len();
+                if n >= 3 {
+                    Some((c, n))
+                } else {
+                    None
+                }
+            });
+
+            match (open.fence, fence) {
+                (Some((c, n)), Some((c2, n2))) => {
+                    // A closing fence has no info string.
+                    if c == c2 && n2 >= n && trimmed[n2..].trim().is_empty() {
+                        open.fence = None;
+                    }
+                }
+                (Some(_), None) => {}
+                (None, Some(fence)) => open.fence = Some(fence),
+                (None, None) => {
+                    open.details += trimmed.matches("<details").count();
+                    open.details = open
+                        .details
+                        .saturating_sub(trimmed.matches("</details>").count());
+                }
+            }
+        }
+
+        open
+    }
+
+    /// Returns the markdown needed to close the open constructs, followed by
+    /// the truncation notice.
+    fn closing(&self, prefix: &str) -> String {
+        let mut closing = String::new();
+
+        if !prefix.is_empty() && !prefix.ends_with('\n') {
+            closing.push('\n');
+        }
+
+        if let Some((c, n)) = self.fence {
+            closing.extend(std::iter::repeat_n(c, n));
+            closing.push('\n');
+        }
+
+        for _ in 0..self.details {
+            closing.push_str("</details>\n");
+        }
+
+        closing.push_str(TRUNCATED_NOTICE);
+        closing
+    }
+}
+
+/// Truncates markdown to at most `max_len` characters.
+///
+/// If the markdown is too long, it is cut at a line boundary where possible,
+/// any open code fences and `<details>` sections are closed, and a notice is
+/// appended saying that the output was truncated. The result is always at
+/// most `max_len` characters long, unless `max_len` is too small to fit the
+/// notice.
+pub fn truncate(markdown: &str, max_len: usize) -> String {
+    if markdown.chars().count() <= max_len {
+        return markdown.to_string();
+    }
+
+    let mut keep = max_len.saturating_sub(TRUNCATED_NOTICE.len() + 1);
+
+    loop {
+        // Find the byte offset of the cut, on a UTF-8 boundary.
+        let end = markdown
+            .char_indices()
+            .nth(keep)
+            .map_or(markdown.len(), |(i, _)| i);
+
+        // Prefer to cut after a complete line so that the last line isn't
+        // mangled, unless that would throw away most of the text.
+        let end = match markdown[..end].rfind('\n') {
+            Some(i) if i + 1 >= end / 2 => i + 1,
+            _ => end,
+        };
+
+        let prefix = &markdown[..end];
+        let closing = OpenBlocks::scan(prefix).closing(prefix);
+
+        let len = prefix.chars().count() + closing.chars().count();
+        if len <= max_len || end == 0 {
+            let mut truncated = String::with_capacity(end + closing.len());
+            truncated.push_str(prefix);
+            truncated.push_str(&closing);
+            return truncated;
+        }
+
+        // Make room for the closing tags and try again.
+        keep = prefix.chars().count().saturating_sub(len - max_len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_inline_characters() {
+        assert_eq!(escape("a *b* _c_ `d`"), r"a \*b\* \_c\_ \`d\`");
+        assert_eq!(
+            escape("[a](b) <c> a|b ~d~ &e"),
+            r"\[a\](b) \<c\> a\|b \~d\~ \&e"
+        );
+        assert_eq!(escape(r"a\b"), r"a\\b");
+    }
+
+    #[test]
+    fn escapes_line_starts() {
+        assert_eq!(escape("# a\n- b\n+ c\n= d"), "\\# a\n\\- b\n\\+ c\n\\= d");
+        assert_eq!(escape("1. a\n  2) b"), "1\\. a\n  2\\) b");
+
+        // Only at the start of a line.
+        assert_eq!(escape("a # b - c 1. d"), "a # b - c 1. d");
+    }
+
+    #[test]
+    fn details_summary_is_html_escaped() {
+        let mut markdown = Markdown::new();
+        markdown.details("a < b && \"c\" > *d*", |inner| {
+            inner.paragraph("e");
+        });
+
+        assert_eq!(
+            markdown.as_str(),
+            "<details>\n<summary>a &lt; b &amp;&amp; &quot;c&quot; &gt; \
+             *d*</summary>\n\ne\n\n</details>\n"
+        );
+    }
+
+    #[test]
+    fn truncate_short() {
+        assert_eq!(truncate("abc\n", 4), "abc\n");
+    }
+
+    #[test]
+    fn truncate_at_line_boundary() {
+        let markdown = "a\n".repeat(100);
+        let truncated = truncate(&markdown, 50);
+
+        assert!(truncated.chars().count() <= 50);
+        assert!(truncated.starts_with("a\na\n"));
+
+        // Only whole lines are kept.
+        let kept = truncated.strip_suffix(TRUNCATED_NOTICE).unwrap();
+        assert_eq!(kept, "a\n".repeat(kept.len() / 2));
+    }
+
+    #[test]
+    fn truncate_closes_blocks() {
+        let mut markdown = Markdown::new();
+        markdown.details("summary", |inner| {
+            inner.code_block("rust", &"let x = 1;\n".repeat(100));
+        });
+
+        let truncated = truncate(markdown.as_str(), 200);
+        assert!(truncated.chars().count() <= 200);
+        assert!(truncated.ends_with(&format!(
+            "let x = 1;\n```\n</details>\n{}",
+            TRUNCATED_NOTICE
+        )));
+    }
+
+    #[test]
+    fn truncate_multibyte() {
+        let truncated = truncate(&"é".repeat(100), 40);
+        assert!(truncated.chars().count() <= 40);
+        assert!(truncated.starts_with("éé"));
+    }
+}
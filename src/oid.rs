@@ -29,6 +29,7 @@ use serde::ser::{self, Serialize, Serializer};
 pub struct Oid([u8; 20]);
 
 impl Oid {
+    #[allow(clippy::result_unit_err)]
     pub fn from_hex(s: &str) -> Result<Self, ()> {
         Ok(Oid(<[u8; 20]>::from_hex(s).map_err(|_| ())?))
     }
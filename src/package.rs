@@ -0,0 +1,271 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! GitHub Packages.
+//!
+//! See: https://docs.github.com/en/rest/packages
+
+use serde::{
+    de::{self, Deserializer},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{DateTime, PackageFileId, PackageId, PackageVersionId, User};
+
+/// The type of a package.
+///
+/// GitHub is inconsistent about the casing of package types (e.g., `npm` vs.
+/// `CONTAINER`), so these are parsed case-insensitively. They are serialized
+/// the way webhook payloads spell them, which isn't always the same as
+/// [`PackageType::name`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+pub enum PackageType {
+    Npm,
+    Maven,
+    Rubygems,
+    Docker,
+    Nuget,
+    Container,
+}
+
+impl PackageType {
+    /// Returns a static string for the package type, as used by the REST API.
+    pub fn name(self) -> &'static str {
+        match self {
+            PackageType::Npm => "npm",
+            PackageType::Maven => "maven",
+            PackageType::Rubygems => "rubygems",
+            PackageType::Docker => "docker",
+            PackageType::Nuget => "nuget",
+            PackageType::Container => "container",
+        }
+    }
+}
+
+impl FromStr for PackageType {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "npm" => Ok(PackageType::Npm),
+            "maven" => Ok(PackageType::Maven),
+            "rubygems" => Ok(PackageType::Rubygems),
+            "docker" => Ok(PackageType::Docker),
+            "nuget" => Ok(PackageType::Nuget),
+            "container" => Ok(PackageType::Container),
+            _ => Err("invalid package type"),
+        }
+    }
+}
+
+impl Serialize for PackageType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Webhook payloads spell this one in upper case.
+        match self {
+            PackageType::Container => serializer.serialize_str("CONTAINER"),
+            _ => serializer.serialize_str(self.name()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PackageType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for PackageType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The registry a package was published to.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackageRegistry {
+    pub about_url: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub registry_type: String,
+    pub url: String,
+    pub vendor: String,
+}
+
+/// A file belonging to a package version.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackageFile {
+    pub id: PackageFileId,
+    pub name: String,
+    pub download_url: String,
+    pub content_type: String,
+    pub state: String,
+    pub size: u64,
+    pub sha1: Option<String>,
+    pub sha256: Option<String>,
+    pub md5: Option<String>,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+/// A container image tag.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContainerTag {
+    /// The name of the tag (e.g., `latest`).
+    pub name: String,
+
+    /// The digest of the manifest the tag points to (e.g., `sha256:...`).
+    pub digest: String,
+}
+
+/// A container image manifest.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContainerManifest {
+    /// The digest of the manifest (e.g., `sha256:...`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+/// Container metadata included with a package version in webhook payloads.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ContainerMetadata {
+    /// The tag that was published or updated.
+    pub tag: Option<ContainerTag>,
+
+    /// The image manifest.
+    pub manifest: Option<ContainerManifest>,
+}
+
+/// A version of a package.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackageVersion {
+    pub id: PackageVersionId,
+
+    /// The version string. For containers, this is the manifest digest.
+    pub version: Option<String>,
+
+    /// The name of the version, as returned by the REST API.
+    pub name: Option<String>,
+
+    pub summary: Option<String>,
+    pub description: Option<String>,
+    pub html_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_url: Option<String>,
+    pub installation_command: Option<String>,
+
+    /// The name of the Git tag the version was published from, if any.
+    pub tag_name: Option<String>,
+
+    /// The commit-ish the version was published from, if any.
+    pub target_commitish: Option<String>,
+
+    pub draft: Option<bool>,
+    pub prerelease: Option<bool>,
+
+    /// Container metadata. This is only present for container packages.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_metadata: Option<ContainerMetadata>,
+
+    #[serde(default)]
+    pub package_files: Vec<PackageFile>,
+
+    /// The user who published this version.
+    pub author: Option<User>,
+
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+}
+
+/// A package.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Package {
+    pub id: PackageId,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub description: Option<String>,
+    pub ecosystem: Option<String>,
+    pub package_type: PackageType,
+    pub html_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    pub owner: User,
+
+    /// The version that was published or updated. This is only present in
+    /// webhook payloads.
+    pub package_version: Option<PackageVersion>,
+
+    /// The registry the package belongs to. This is only present in webhook
+    /// payloads.
+    pub registry: Option<PackageRegistry>,
+
+    pub created_at: DateTime,
+    pub updated_at: Option<DateTime>,
+}
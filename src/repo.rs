@@ -18,23 +18,35 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{DateTime, Oid, User};
+use crate::{
+    CommentId, DateTime, DeployKeyId, IssueId, IssueNumber, MilestoneId,
+    MilestoneNumber, Oid, PullRequestId, RepoId, ReviewCommentId, ReviewId,
+    User,
+};
 
 /// Short info about a repository.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ShortRepo {
-    pub id: u64,
+    pub id: RepoId,
     pub name: String,
     pub full_name: String,
     pub private: bool,
 }
 
 /// A repository.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Repository {
-    pub id: u64,
+    pub id: RepoId,
     pub owner: User,
     pub name: String,
     pub full_name: String,
@@ -102,9 +114,31 @@ pub struct Repository {
     pub updated_at: DateTime,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// An SSH key granting access to a single repository.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct DeployKey {
+    pub id: DeployKeyId,
+    pub key: String,
+    pub url: String,
+    pub title: String,
+    pub verified: bool,
+    pub read_only: bool,
+    pub added_by: Option<String>,
+    pub last_used: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Comment {
-    pub id: u64,
+    pub id: CommentId,
     pub url: String,
     pub html_url: String,
     pub body: String,
@@ -113,9 +147,13 @@ pub struct Comment {
     pub updated_at: DateTime,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRequest {
-    pub id: u64,
+    pub id: PullRequestId,
     pub url: String,
     pub html_url: String,
     pub diff_url: String,
@@ -126,7 +164,7 @@ pub struct PullRequest {
     pub review_comment_url: String,
     pub comments_url: String,
     pub statuses_url: String,
-    pub number: u64,
+    pub number: IssueNumber,
     pub state: String,
     pub title: String,
     pub body: Option<String>,
@@ -151,7 +189,11 @@ pub struct PullRequest {
     pub labels: Vec<Label>,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ShortCommit {
     pub label: String,
     #[serde(rename = "ref")]
@@ -160,22 +202,53 @@ pub struct ShortCommit {
     pub user: User,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Label {
     pub url: String,
     pub name: String,
     pub color: String,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Milestone {
+    pub id: MilestoneId,
+    pub url: String,
+    pub html_url: String,
+    pub labels_url: String,
+    pub number: MilestoneNumber,
+    pub state: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub creator: Option<User>,
+    pub open_issues: u64,
+    pub closed_issues: u64,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub closed_at: Option<DateTime>,
+    pub due_on: Option<DateTime>,
+}
+
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Issue {
-    pub id: u64,
+    pub id: IssueId,
     pub url: String,
     pub labels_url: String,
     pub comments_url: String,
     pub events_url: String,
     pub html_url: String,
-    pub number: u64,
+    pub number: IssueNumber,
     pub state: String,
     pub title: String,
     pub body: Option<String>,
@@ -184,6 +257,7 @@ pub struct Issue {
     pub assignee: Option<User>,
     pub locked: bool,
     pub comments: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pull_request: Option<PullRef>,
     pub closed_at: Option<DateTime>,
     pub created_at: DateTime,
@@ -192,7 +266,11 @@ pub struct Issue {
 }
 
 /// A reference to a pull request.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PullRef {
     pub url: String,
     pub html_url: String,
@@ -200,23 +278,176 @@ pub struct PullRef {
     pub patch_url: String,
 }
 
+/// The state of a pull request review.
+///
+/// Webhooks use lowercase states while the REST API uses uppercase states.
+/// Both are accepted.
 #[derive(
-    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
 )]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewState {
+    #[serde(alias = "APPROVED")]
+    Approved,
+    #[serde(alias = "CHANGES_REQUESTED")]
+    ChangesRequested,
+    #[serde(alias = "COMMENTED")]
     Commented,
+    #[serde(alias = "DISMISSED")]
+    Dismissed,
+    #[serde(alias = "PENDING")]
+    Pending,
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Review {
-    pub id: u64,
+    pub id: ReviewId,
     pub user: User,
     pub body: Option<String>,
     pub commit_id: Oid,
-    pub submitted_at: DateTime,
+    /// The time the review was submitted. This is `None` if the review is
+    /// still pending.
+    pub submitted_at: Option<DateTime>,
     pub state: ReviewState,
     pub html_url: String,
     pub pull_request_url: String,
     pub author_association: String,
 }
+
+/// The side of a diff that a review comment applies to.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Side {
+    /// The old version of the file (i.e., deletions and unchanged lines).
+    Left,
+
+    /// The new version of the file (i.e., additions and unchanged lines).
+    Right,
+}
+
+/// Whether a review comment applies to specific lines or to an entire file.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewCommentSubject {
+    Line,
+    File,
+}
+
+/// A comment on a pull request's unified diff.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReviewComment {
+    pub id: ReviewCommentId,
+    pub url: String,
+    pub html_url: String,
+    pub pull_request_url: String,
+
+    /// The ID of the review this comment belongs to.
+    pub pull_request_review_id: Option<ReviewId>,
+
+    /// The ID of the comment this is a reply to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_id: Option<ReviewCommentId>,
+
+    /// The diff of the line that the comment refers to.
+    pub diff_hunk: String,
+
+    /// The relative path of the file the comment applies to.
+    pub path: String,
+
+    /// The line index into the diff this comment applies to. This is `None`
+    /// if the comment is outdated.
+    pub position: Option<u32>,
+
+    /// The line index into the diff this comment originally applied to.
+    pub original_position: Option<u32>,
+
+    /// The SHA of the commit this comment applies to.
+    pub commit_id: Oid,
+
+    /// The SHA of the commit this comment was originally made on.
+    pub original_commit_id: Oid,
+
+    /// The first line of a multi-line comment.
+    pub start_line: Option<u32>,
+
+    /// The first line of a multi-line comment when it was originally made.
+    pub original_start_line: Option<u32>,
+
+    /// The side of the first line of a multi-line comment.
+    pub start_side: Option<Side>,
+
+    /// The line of the file this comment applies to. For multi-line comments,
+    /// this is the last line.
+    pub line: Option<u32>,
+
+    /// The line of the file this comment originally applied to.
+    pub original_line: Option<u32>,
+
+    /// The side of the diff this comment applies to.
+    pub side: Option<Side>,
+
+    /// Whether the comment applies to a line or the whole file.
+    pub subject_type: Option<ReviewCommentSubject>,
+
+    pub body: String,
+    pub user: User,
+    pub author_association: String,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+/// A thread of review comments.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReviewThread {
+    pub node_id: String,
+    pub comments: Vec<ReviewComment>,
+}
@@ -0,0 +1,208 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::collections::BTreeMap;
+
+use crate::{Review, ReviewState};
+
+/// The overall review decision of a pull request, as shown in the merge box.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum ReviewDecision {
+    /// At least one reviewer approved and nobody requested changes.
+    Approved,
+
+    /// At least one reviewer requested changes.
+    ChangesRequested,
+
+    /// Nobody has approved or requested changes yet.
+    ReviewRequired,
+}
+
+impl ReviewDecision {
+    /// Computes the review decision from all of the reviews on a pull
+    /// request.
+    ///
+    /// Only the latest approval, change request, or dismissal of each user
+    /// counts. Comments and pending reviews never change a user's verdict, and
+    /// a dismissed review clears it.
+    pub fn from_reviews(reviews: &[Review]) -> Self {
+        let latest = latest_reviews(reviews);
+
+        if latest
+            .iter()
+            .any(|r| r.state == ReviewState::ChangesRequested)
+        {
+            ReviewDecision::ChangesRequested
+        } else if latest.iter().any(|r| r.state == ReviewState::Approved) {
+            ReviewDecision::Approved
+        } else {
+            ReviewDecision::ReviewRequired
+        }
+    }
+}
+
+/// Returns the review that currently determines each user's verdict, ordered
+/// by user ID.
+///
+/// This is the latest submitted review of each user that either approved or
+/// requested changes. Users whose latest such review was dismissed are
+/// omitted.
+pub fn latest_reviews(reviews: &[Review]) -> Vec<&Review> {
+    let mut sorted: Vec<&Review> = reviews
+        .iter()
+        .filter(|r| match r.state {
+            ReviewState::Approved
+            | ReviewState::ChangesRequested
+            | ReviewState::Dismissed => r.submitted_at.is_some(),
+            ReviewState::Commented | ReviewState::Pending => false,
+        })
+        .collect();
+
+    sorted
+        .sort_by(|a, b| (&a.submitted_at, a.id).cmp(&(&b.submitted_at, b.id)));
+
+    let mut latest = BTreeMap::new();
+
+    for review in sorted {
+        latest.insert(review.user.id, review);
+    }
+
+    latest
+        .into_values()
+        .filter(|r| r.state != ReviewState::Dismissed)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures;
+    use crate::{DateTime, Event, EventType, ReviewId, UserId};
+
+    /// Returns a review by `user`, submitted `at` seconds since the epoch.
+    fn review(
+        id: u64,
+        user: u64,
+        state: ReviewState,
+        at: Option<i64>,
+    ) -> Review {
+        let fixture =
+            fixtures::get(EventType::PullRequestReview, Some("submitted"))
+                .unwrap();
+
+        let mut review = match fixture.event() {
+            Event::PullRequestReview(event) => event.review,
+            event => panic!("unexpected event {:?}", event),
+        };
+
+        review.id = ReviewId(id);
+        review.user.id = UserId(user);
+        review.state = state;
+        review.submitted_at = at.and_then(DateTime::from_timestamp);
+        review
+    }
+
+    fn ids(reviews: &[Review]) -> Vec<ReviewId> {
+        latest_reviews(reviews).iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn latest_review_per_user() {
+        let reviews = [
+            review(1, 10, ReviewState::ChangesRequested, Some(100)),
+            review(2, 20, ReviewState::Approved, Some(150)),
+            review(3, 10, ReviewState::Approved, Some(200)),
+            // Comments don't change a verdict.
+            review(4, 10, ReviewState::Commented, Some(300)),
+        ];
+
+        assert_eq!(ids(&reviews), [ReviewId(3), ReviewId(2)]);
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews),
+            ReviewDecision::Approved
+        );
+
+        // The order of the input doesn't matter.
+        let mut reversed = reviews.clone();
+        reversed.reverse();
+        assert_eq!(ids(&reversed), [ReviewId(3), ReviewId(2)]);
+    }
+
+    #[test]
+    fn changes_requested_wins() {
+        let reviews = [
+            review(1, 10, ReviewState::Approved, Some(100)),
+            review(2, 20, ReviewState::ChangesRequested, Some(100)),
+        ];
+
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews),
+            ReviewDecision::ChangesRequested
+        );
+    }
+
+    #[test]
+    fn dismissed_reviews_drop_out() {
+        let reviews = [
+            review(1, 10, ReviewState::ChangesRequested, Some(100)),
+            review(2, 10, ReviewState::Dismissed, Some(200)),
+        ];
+
+        assert!(ids(&reviews).is_empty());
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews),
+            ReviewDecision::ReviewRequired
+        );
+    }
+
+    #[test]
+    fn ties_are_broken_by_id() {
+        let reviews = [
+            review(2, 10, ReviewState::Approved, Some(100)),
+            review(1, 10, ReviewState::ChangesRequested, Some(100)),
+        ];
+
+        assert_eq!(ids(&reviews), [ReviewId(2)]);
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews),
+            ReviewDecision::Approved
+        );
+    }
+
+    #[test]
+    fn unsubmitted_reviews_are_ignored() {
+        let reviews = [
+            review(1, 10, ReviewState::Approved, Some(100)),
+            review(2, 10, ReviewState::ChangesRequested, None),
+            review(3, 20, ReviewState::Pending, None),
+        ];
+
+        assert_eq!(ids(&reviews), [ReviewId(1)]);
+        assert_eq!(
+            ReviewDecision::from_reviews(&reviews),
+            ReviewDecision::Approved
+        );
+        assert_eq!(
+            ReviewDecision::from_reviews(&[]),
+            ReviewDecision::ReviewRequired
+        );
+    }
+}
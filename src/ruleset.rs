@@ -0,0 +1,785 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Repository rulesets.
+//!
+//! See: https://docs.github.com/en/rest/repos/rules
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AppId, DateTime, RulesetId};
+
+/// The kind of refs a ruleset targets.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RulesetTarget {
+    Branch,
+    Tag,
+    Push,
+}
+
+impl RulesetTarget {
+    /// Returns the ref prefix matched by this target, if any. Push rulesets
+    /// are not scoped to refs.
+    pub fn ref_prefix(self) -> Option<&'static str> {
+        match self {
+            RulesetTarget::Branch => Some("refs/heads/"),
+            RulesetTarget::Tag => Some("refs/tags/"),
+            RulesetTarget::Push => None,
+        }
+    }
+}
+
+/// Where a ruleset was defined.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum RulesetSourceType {
+    Repository,
+    Organization,
+    Enterprise,
+}
+
+/// How a ruleset is enforced.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum Enforcement {
+    /// The ruleset is not enforced.
+    Disabled,
+
+    /// The ruleset is enforced.
+    Active,
+
+    /// The ruleset is evaluated, but violations are only reported. This is
+    /// only available to organizations on GitHub Enterprise.
+    Evaluate,
+}
+
+/// The kind of actor that may bypass a ruleset.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum BypassActorType {
+    Integration,
+    OrganizationAdmin,
+    RepositoryRole,
+    Team,
+    DeployKey,
+}
+
+/// When an actor may bypass a ruleset.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BypassMode {
+    /// The actor can always bypass the ruleset.
+    Always,
+
+    /// The actor can only bypass the ruleset through a pull request.
+    PullRequest,
+}
+
+/// An actor that may bypass a ruleset.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BypassActor {
+    /// The ID of the actor. This is `None` for organization admins and deploy
+    /// keys.
+    ///
+    /// This is a plain `u64` rather than one of the ID types in [`crate::id`]
+    /// on purpose: depending on `actor_type`, it is the ID of an app, a team
+    /// or a repository role.
+    pub actor_id: Option<u64>,
+    pub actor_type: BypassActorType,
+    pub bypass_mode: BypassMode,
+}
+
+/// Ref name patterns to include or exclude.
+///
+/// Patterns use `fnmatch` syntax and are matched against the full ref name
+/// (e.g., `refs/heads/main`). The special patterns `~DEFAULT_BRANCH` and `~ALL`
+/// match the repository's default branch and all refs, respectively.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RefNameCondition {
+    /// A ref must match at least one of these patterns.
+    pub include: Vec<String>,
+
+    /// A ref must not match any of these patterns.
+    pub exclude: Vec<String>,
+}
+
+impl RefNameCondition {
+    /// Returns `true` if the fully qualified `git_ref` matches this condition.
+    ///
+    /// `default_branch` is the short name of the repository's default branch
+    /// (e.g., `main`) and is used to expand `~DEFAULT_BRANCH`.
+    pub fn matches(&self, git_ref: &str, default_branch: &str) -> bool {
+        let is_match = |pattern: &String| match pattern.as_str() {
+            "~ALL" => true,
+            "~DEFAULT_BRANCH" => git_ref
+                .strip_prefix("refs/heads/")
+                .is_some_and(|branch| branch == default_branch),
+            pattern => fnmatch(pattern, git_ref),
+        };
+
+        self.include.iter().any(is_match) && !self.exclude.iter().any(is_match)
+    }
+}
+
+/// Conditions that determine which refs a ruleset applies to.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RulesetConditions {
+    pub ref_name: Option<RefNameCondition>,
+}
+
+/// Parameters for the `update` rule.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UpdateRuleParameters {
+    /// Branch can pull changes from its upstream repository.
+    pub update_allows_fetch_and_merge: bool,
+}
+
+/// Parameters for the `required_deployments` rule.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequiredDeploymentsParameters {
+    /// The environments that must be successfully deployed to before refs can
+    /// be pushed.
+    pub required_deployment_environments: Vec<String>,
+}
+
+/// Parameters for the `pull_request` rule.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PullRequestRuleParameters {
+    /// New, reviewable commits pushed will dismiss previous pull request
+    /// review approvals.
+    pub dismiss_stale_reviews_on_push: bool,
+
+    /// Require an approving review in pull requests that modify files that
+    /// have a designated code owner.
+    pub require_code_owner_review: bool,
+
+    /// Whether the most recent reviewable push must be approved by someone
+    /// other than the person who pushed it.
+    pub require_last_push_approval: bool,
+
+    /// The number of approving reviews that are required before a pull
+    /// request can be merged.
+    pub required_approving_review_count: u32,
+
+    /// All conversations on code must be resolved before a pull request can
+    /// be merged.
+    pub required_review_thread_resolution: bool,
+}
+
+/// A status check that must pass.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StatusCheckConfiguration {
+    /// The status check context name that must be present on the commit.
+    pub context: String,
+
+    /// The optional ID of the integration that must provide this status check.
+    pub integration_id: Option<AppId>,
+}
+
+/// Parameters for the `required_status_checks` rule.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RequiredStatusChecksParameters {
+    /// Status checks that are required.
+    pub required_status_checks: Vec<StatusCheckConfiguration>,
+
+    /// Whether pull requests targeting a matching branch must be tested with
+    /// the latest code.
+    pub strict_required_status_checks_policy: bool,
+}
+
+/// The operator used by a pattern rule.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PatternOperator {
+    StartsWith,
+    EndsWith,
+    Contains,
+    Regex,
+}
+
+/// Parameters for rules that match a pattern, such as
+/// `commit_message_pattern`.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PatternRuleParameters {
+    /// How this rule will appear to users.
+    pub name: Option<String>,
+
+    /// If `true`, the rule will fail if the pattern matches.
+    #[serde(default)]
+    pub negate: bool,
+
+    /// The operator to use for matching.
+    pub operator: PatternOperator,
+
+    /// The pattern to match with.
+    pub pattern: String,
+}
+
+/// A rule enforced by a ruleset.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", content = "parameters", rename_all = "snake_case")]
+pub enum Rule {
+    /// Only allow users with bypass permission to create matching refs.
+    Creation,
+
+    /// Only allow users with bypass permission to update matching refs.
+    /// GitHub leaves out the parameters if none were set.
+    Update(Option<UpdateRuleParameters>),
+
+    /// Only allow users with bypass permissions to delete matching refs.
+    Deletion,
+
+    /// Prevent merge commits from being pushed to matching refs.
+    RequiredLinearHistory,
+
+    /// Choose which environments must be successfully deployed to before refs
+    /// can be pushed into a ref that matches this rule.
+    RequiredDeployments(RequiredDeploymentsParameters),
+
+    /// Commits pushed to matching refs must have verified signatures.
+    RequiredSignatures,
+
+    /// Require all commits be made to a non-target branch and submitted via a
+    /// pull request before they can be merged.
+    PullRequest(PullRequestRuleParameters),
+
+    /// Choose which status checks must pass before the ref is updated.
+    RequiredStatusChecks(RequiredStatusChecksParameters),
+
+    /// Prevent users with push access from force pushing to refs.
+    NonFastForward,
+
+    /// Parameters to be used for the `commit_message_pattern` rule.
+    CommitMessagePattern(PatternRuleParameters),
+
+    /// Parameters to be used for the `commit_author_email_pattern` rule.
+    CommitAuthorEmailPattern(PatternRuleParameters),
+
+    /// Parameters to be used for the `committer_email_pattern` rule.
+    CommitterEmailPattern(PatternRuleParameters),
+
+    /// Parameters to be used for the `branch_name_pattern` rule.
+    BranchNamePattern(PatternRuleParameters),
+
+    /// Parameters to be used for the `tag_name_pattern` rule.
+    TagNamePattern(PatternRuleParameters),
+
+    /// A rule that isn't known yet, or whose parameters couldn't be parsed.
+    #[serde(untagged)]
+    Unknown(UnknownRule),
+}
+
+/// A rule that isn't known yet. Its parameters are not kept.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UnknownRule {
+    /// The type of the rule (e.g., `merge_queue`).
+    #[serde(rename = "type")]
+    #[cfg_attr(
+        feature = "arbitrary",
+        proptest(regex = "unknown_[a-z_]{0,16}")
+    )]
+    pub rule_type: String,
+}
+
+/// A repository ruleset.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Ruleset {
+    /// The ID of the ruleset.
+    pub id: RulesetId,
+
+    /// The name of the ruleset.
+    pub name: String,
+
+    /// The kind of refs the ruleset applies to.
+    #[serde(default = "default_target")]
+    pub target: RulesetTarget,
+
+    /// Where the ruleset was defined.
+    pub source_type: Option<RulesetSourceType>,
+
+    /// The name of the source (e.g., `owner/repo` or the organization name).
+    pub source: String,
+
+    /// How the ruleset is enforced.
+    pub enforcement: Enforcement,
+
+    /// The actors that can bypass the rules in this ruleset.
+    #[serde(default)]
+    pub bypass_actors: Vec<BypassActor>,
+
+    /// The conditions under which the ruleset applies. If there are no
+    /// conditions, the ruleset does not target any refs.
+    pub conditions: Option<RulesetConditions>,
+
+    /// The rules enforced by the ruleset.
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+
+    pub node_id: Option<String>,
+    pub created_at: Option<DateTime>,
+    pub updated_at: Option<DateTime>,
+}
+
+fn default_target() -> RulesetTarget {
+    RulesetTarget::Branch
+}
+
+impl Ruleset {
+    /// Returns `true` if this ruleset targets the fully qualified `git_ref`
+    /// (e.g., `PushEvent::git_ref`).
+    ///
+    /// `default_branch` is the short name of the repository's default branch
+    /// and is used to expand `~DEFAULT_BRANCH`. Push rulesets apply to every
+    /// ref. Note that this does not take `enforcement` into account.
+    pub fn applies_to(&self, git_ref: &str, default_branch: &str) -> bool {
+        let prefix = match self.target.ref_prefix() {
+            Some(prefix) => prefix,
+            None => return true,
+        };
+
+        if !git_ref.starts_with(prefix) {
+            return false;
+        }
+
+        self.conditions
+            .as_ref()
+            .and_then(|c| c.ref_name.as_ref())
+            .is_some_and(|c| c.matches(git_ref, default_branch))
+    }
+}
+
+/// Matches `name` against an `fnmatch`-style `pattern` using the semantics
+/// GitHub uses for ruleset ref patterns:
+///
+///  - `*` matches any sequence of characters except `/`.
+///  - `**` matches any sequence of characters, including `/`. When followed by
+///    a `/`, it may also match zero directories.
+///  - `?` matches any single character except `/`.
+///  - `[...]` matches a character class, which may be negated with `!` or `^`.
+///  - `\` escapes the next character.
+///
+/// This runs in `O(pattern * name)` time, regardless of how many wildcards
+/// there are.
+fn fnmatch(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let tokens = tokenize(&pattern);
+
+    // `states[i]` is `true` if the first `i` tokens match the part of the
+    // name seen so far.
+    let mut states = vec![false; tokens.len() + 1];
+    states[0] = true;
+    skip_empty(&tokens, &mut states);
+
+    for c in name.chars() {
+        let mut next = vec![false; tokens.len() + 1];
+
+        for (i, token) in tokens.iter().enumerate() {
+            if !states[i] {
+                continue;
+            }
+
+            match *token {
+                Token::Char(x) => next[i + 1] |= c == x,
+                Token::Any => next[i + 1] |= c != '/',
+                Token::Class(start) => {
+                    next[i + 1] |= match_class(&pattern[start..], Some(&c))
+                        .is_some_and(|(matched, _)| matched);
+                }
+                Token::Star => next[i] |= c != '/',
+                Token::DoubleStar => next[i] = true,
+                Token::SkipDirs => {}
+            }
+        }
+
+        skip_empty(&tokens, &mut next);
+
+        if !next.contains(&true) {
+            return false;
+        }
+
+        states = next;
+    }
+
+    states[tokens.len()]
+}
+
+/// A single element of an `fnmatch` pattern.
+enum Token {
+    /// A literal character.
+    Char(char),
+
+    /// `?`
+    Any,
+
+    /// `[...]`, starting at the given index just after the `[`.
+    Class(usize),
+
+    /// `*`
+    Star,
+
+    /// `**`
+    DoubleStar,
+
+    /// Placed before the `**` and `/` of `**/` so that they can be skipped to
+    /// match zero directories.
+    SkipDirs,
+}
+
+fn tokenize(p: &[char]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < p.len() {
+        let (token, len) = match p[i] {
+            '*' if p.get(i + 1) == Some(&'*') => {
+                if p.get(i + 2) == Some(&'/') {
+                    tokens.push(Token::SkipDirs);
+                }
+
+                (Token::DoubleStar, 2)
+            }
+            '*' => (Token::Star, 1),
+            '?' => (Token::Any, 1),
+            '[' => match match_class(&p[i + 1..], None) {
+                Some((_, len)) => (Token::Class(i + 1), 1 + len),
+                // An unterminated class is matched literally.
+                None => (Token::Char('['), 1),
+            },
+            '\\' if i + 1 < p.len() => (Token::Char(p[i + 1]), 2),
+            c => (Token::Char(c), 1),
+        };
+
+        tokens.push(token);
+        i += len;
+    }
+
+    tokens
+}
+
+/// Marks the states reachable by matching wildcards against nothing.
+fn skip_empty(tokens: &[Token], states: &mut [bool]) {
+    for (i, token) in tokens.iter().enumerate() {
+        if states[i] {
+            match token {
+                Token::Star | Token::DoubleStar => states[i + 1] = true,
+                Token::SkipDirs => {
+                    states[i + 1] = true;
+                    states[i + 3] = true;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Matches a character class, where `p` starts just after the opening `[`.
+/// Returns whether the character matched along with the length of the class
+/// (including the closing `]`), or `None` if the class is unterminated.
+fn match_class(p: &[char], c: Option<&char>) -> Option<(bool, usize)> {
+    let mut i = 0;
+
+    let negated = match p.first() {
+        Some('!') | Some('^') => {
+            i += 1;
+            true
+        }
+        _ => false,
+    };
+
+    let mut matched = false;
+    let mut first = true;
+
+    loop {
+        let mut lo = *p.get(i)?;
+
+        if lo == ']' && !first {
+            break;
+        }
+
+        first = false;
+
+        if lo == '\\' {
+            i += 1;
+            lo = *p.get(i)?;
+        }
+
+        let mut hi = lo;
+
+        if p.get(i + 1) == Some(&'-') && p.get(i + 2).is_some_and(|&c| c != ']')
+        {
+            i += 2;
+            hi = p[i];
+
+            if hi == '\\' {
+                i += 1;
+                hi = *p.get(i)?;
+            }
+        }
+
+        if let Some(&c) = c {
+            if lo <= c && c <= hi {
+                matched = true;
+            }
+        }
+
+        i += 1;
+    }
+
+    // A class never matches a path separator.
+    if c == Some(&'/') {
+        matched = negated;
+    }
+
+    Some((matched != negated, i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnmatch_wildcards() {
+        assert!(fnmatch("refs/heads/*", "refs/heads/main"));
+        assert!(!fnmatch("refs/heads/*", "refs/heads/feature/a"));
+        assert!(fnmatch("refs/heads/**", "refs/heads/feature/a"));
+        assert!(fnmatch("refs/heads/**/a", "refs/heads/a"));
+        assert!(fnmatch("refs/heads/**/a", "refs/heads/x/y/a"));
+        assert!(!fnmatch("refs/heads/**/a", "refs/heads/xa"));
+        assert!(fnmatch("refs/heads/release-?", "refs/heads/release-1"));
+        assert!(!fnmatch("refs/heads/release-?", "refs/heads/release-"));
+        assert!(!fnmatch("refs/heads/a?b", "refs/heads/a/b"));
+        assert!(fnmatch("*", ""));
+        assert!(!fnmatch("", "a"));
+    }
+
+    #[test]
+    fn fnmatch_classes() {
+        assert!(fnmatch("v[0-9]", "v5"));
+        assert!(!fnmatch("v[0-9]", "vx"));
+        assert!(fnmatch("v[!0-9]", "vx"));
+        assert!(fnmatch("v[^0-9]", "vx"));
+        assert!(!fnmatch("v[!0-9]", "v5"));
+        assert!(fnmatch("[]a]", "]"));
+        assert!(!fnmatch("a[!x]b", "a/b"));
+
+        // An unterminated class is matched literally.
+        assert!(fnmatch("a[b", "a[b"));
+        assert!(!fnmatch("a[b", "ab"));
+    }
+
+    #[test]
+    fn fnmatch_escapes() {
+        assert!(fnmatch(r"a\*", "a*"));
+        assert!(!fnmatch(r"a\*", "ab"));
+        assert!(fnmatch(r"a\?", "a?"));
+        assert!(fnmatch(r"a\", r"a\"));
+    }
+
+    #[test]
+    fn fnmatch_many_wildcards() {
+        // This would take exponential time with a backtracking matcher.
+        let pattern = "*a".repeat(30) + "b";
+        assert!(!fnmatch(&pattern, &"a".repeat(100)));
+
+        let pattern = "**a".repeat(30) + "b";
+        assert!(!fnmatch(&pattern, &"a/".repeat(100)));
+    }
+
+    #[test]
+    fn special_patterns() {
+        let condition = RefNameCondition {
+            include: vec!["~DEFAULT_BRANCH".to_string()],
+            exclude: vec![],
+        };
+        assert!(condition.matches("refs/heads/main", "main"));
+        assert!(!condition.matches("refs/heads/dev", "main"));
+        assert!(!condition.matches("refs/tags/main", "main"));
+
+        let condition = RefNameCondition {
+            include: vec!["~ALL".to_string()],
+            exclude: vec!["refs/heads/wip/**".to_string()],
+        };
+        assert!(condition.matches("refs/heads/main", "main"));
+        assert!(condition.matches("refs/tags/v1", "main"));
+        assert!(!condition.matches("refs/heads/wip/a/b", "main"));
+
+        let condition = RefNameCondition {
+            include: vec!["~ALL".to_string()],
+            exclude: vec!["~DEFAULT_BRANCH".to_string()],
+        };
+        assert!(!condition.matches("refs/heads/main", "main"));
+        assert!(condition.matches("refs/heads/dev", "main"));
+    }
+
+    #[test]
+    fn rules() {
+        let rules: Vec<Rule> = serde_json::from_str(
+            r#"[
+                {"type": "creation"},
+                {"type": "update"},
+                {
+                    "type": "update",
+                    "parameters": {"update_allows_fetch_and_merge": true}
+                },
+                {"type": "merge_queue", "parameters": {"grouping": "ALLGREEN"}}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rules,
+            [
+                Rule::Creation,
+                Rule::Update(None),
+                Rule::Update(Some(UpdateRuleParameters {
+                    update_allows_fetch_and_merge: true
+                })),
+                Rule::Unknown(UnknownRule {
+                    rule_type: "merge_queue".to_string()
+                }),
+            ]
+        );
+    }
+}
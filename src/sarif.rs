@@ -0,0 +1,775 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Conversion of SARIF 2.1.0 logs into check run output.
+//!
+//! Only the subset of SARIF needed to produce annotations is modelled. Unknown
+//! properties are ignored.
+//!
+//! See: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+use serde::Deserialize;
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+use crate::check_run_request::truncate_annotation_text;
+use crate::markdown::{self, Alignment, Markdown, Table};
+use crate::{Annotation, AnnotationLevel, Output};
+
+/// The top-level SARIF log object.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Log {
+    /// The SARIF format version. This should be `2.1.0`.
+    pub version: String,
+
+    /// The runs contained in this log.
+    #[serde(default)]
+    pub runs: Vec<Run>,
+}
+
+/// A single invocation of an analysis tool.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Run {
+    pub tool: Tool,
+
+    /// The results produced by the tool. This is absent if the tool failed to
+    /// run.
+    #[serde(default)]
+    pub results: Vec<SarifResult>,
+}
+
+/// The analysis tool that was run.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+/// The tool component (usually the driver) that produced the results.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolComponent {
+    /// The name of the tool (e.g., `ESLint`).
+    pub name: String,
+    pub version: Option<String>,
+    pub semantic_version: Option<String>,
+    pub information_uri: Option<String>,
+
+    /// The rules the tool can report.
+    #[serde(default)]
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+/// Metadata about a rule.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    /// A stable, opaque identifier for the rule (e.g., `no-unused-vars`).
+    pub id: String,
+
+    /// A human readable name for the rule (e.g., `NoUnusedVars`).
+    pub name: Option<String>,
+
+    pub short_description: Option<MultiformatMessageString>,
+    pub full_description: Option<MultiformatMessageString>,
+
+    /// Help text for the rule.
+    pub help: Option<MultiformatMessageString>,
+    pub help_uri: Option<String>,
+
+    /// The default configuration of the rule.
+    pub default_configuration: Option<ReportingConfiguration>,
+}
+
+impl ReportingDescriptor {
+    /// Returns the most specific description of the rule available.
+    fn description(&self) -> Option<&str> {
+        self.short_description
+            .as_ref()
+            .or(self.full_description.as_ref())
+            .map(|d| d.text.as_str())
+    }
+
+    /// Returns the help text of the rule, followed by the help URI.
+    fn help_text(&self) -> Option<String> {
+        let text = self
+            .help
+            .as_ref()
+            .or(self.full_description.as_ref())
+            .map(|h| h.text.as_str());
+
+        match (text, &self.help_uri) {
+            (Some(text), Some(uri)) => Some(format!("{}\n\n{}", text, uri)),
+            (Some(text), None) => Some(text.to_string()),
+            (None, Some(uri)) => Some(uri.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The configuration of a rule.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ReportingConfiguration {
+    /// The default level of results for the rule.
+    pub level: Option<Level>,
+}
+
+/// A message with plain text and, optionally, markdown.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MultiformatMessageString {
+    pub text: String,
+    pub markdown: Option<String>,
+}
+
+/// The severity of a result.
+#[derive(
+    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum Level {
+    None,
+    Note,
+    Warning,
+    Error,
+}
+
+impl From<Level> for AnnotationLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::None | Level::Note => AnnotationLevel::Notice,
+            Level::Warning => AnnotationLevel::Warning,
+            Level::Error => AnnotationLevel::Failure,
+        }
+    }
+}
+
+/// The nature of a result.
+#[derive(
+    Deserialize, Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ResultKind {
+    NotApplicable,
+    Pass,
+    Fail,
+    Review,
+    Open,
+    Informational,
+}
+
+/// A reference to a rule.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ReportingDescriptorReference {
+    pub id: Option<String>,
+    pub index: Option<usize>,
+}
+
+/// A message describing a result.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Message {
+    pub text: Option<String>,
+    pub markdown: Option<String>,
+
+    /// Values substituted for the `{0}`, `{1}`, etc. placeholders in the
+    /// text.
+    #[serde(default)]
+    pub arguments: Vec<String>,
+}
+
+impl Message {
+    /// Returns the plain text of the message with its arguments substituted.
+    ///
+    /// Literal braces are escaped as `{{` and `}}`. Placeholders without a
+    /// corresponding argument are left as is.
+    fn format(&self) -> Option<String> {
+        let text = self.text.as_ref()?;
+
+        let mut formatted = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+
+        while let Some(start) = rest.find(['{', '}']) {
+            formatted.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                formatted.push_str(&rest[..1]);
+                rest = &rest[2..];
+                continue;
+            }
+
+            let arg = rest
+                .strip_prefix('{')
+                .and_then(|r| Some(&r[..r.find('}')?]))
+                .filter(|i| {
+                    !i.is_empty() && i.bytes().all(|b| b.is_ascii_digit())
+                })
+                .and_then(|i| {
+                    Some((
+                        self.arguments.get(i.parse::<usize>().ok()?)?,
+                        i.len(),
+                    ))
+                });
+
+            match arg {
+                Some((arg, len)) => {
+                    formatted.push_str(arg);
+                    rest = &rest[len + 2..];
+                }
+                None => {
+                    formatted.push_str(&rest[..1]);
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        formatted.push_str(rest);
+
+        Some(formatted)
+    }
+}
+
+/// A location where a result was detected.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub physical_location: Option<PhysicalLocation>,
+}
+
+/// A location within a file.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: Option<ArtifactLocation>,
+    pub region: Option<Region>,
+}
+
+/// The location of a file.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLocation {
+    /// The URI of the file. This is usually relative to the repository root
+    /// (identified by `uri_base_id`), but may also be a `file://` URI.
+    pub uri: Option<String>,
+    pub uri_base_id: Option<String>,
+}
+
+impl ArtifactLocation {
+    /// Returns the path of the file relative to the workspace root. Returns
+    /// `None` if the file is outside of the workspace.
+    fn path(&self, workspace_root: &Path) -> Option<String> {
+        let uri = self.uri.as_ref()?;
+        let uri = percent_decode(uri.strip_prefix("file://").unwrap_or(uri))?;
+
+        let path = Path::new(&uri);
+        let path = if path.is_absolute() {
+            path.strip_prefix(workspace_root).ok()?
+        } else {
+            path
+        };
+
+        let components = path
+            .components()
+            .filter(|c| *c != Component::CurDir)
+            .map(|c| match c {
+                Component::Normal(c) => c.to_str(),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if components.is_empty() {
+            return None;
+        }
+
+        Some(components.join("/"))
+    }
+}
+
+/// A region within a file. Lines and columns are 1-based.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: Option<u32>,
+    pub start_column: Option<u32>,
+
+    /// The end line. Defaults to `start_line` if absent.
+    pub end_line: Option<u32>,
+
+    /// The end column (exclusive).
+    pub end_column: Option<u32>,
+}
+
+/// A result produced by an analysis tool.
+#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    /// The ID of the rule that was violated.
+    pub rule_id: Option<String>,
+
+    /// The index of the rule in the tool's `rules` array.
+    pub rule_index: Option<usize>,
+
+    /// A reference to the rule that was violated.
+    pub rule: Option<ReportingDescriptorReference>,
+
+    /// The severity of the result. If absent, this defaults to the rule's
+    /// configured level or `warning`.
+    pub level: Option<Level>,
+
+    /// The nature of the result. Defaults to `fail`.
+    pub kind: Option<ResultKind>,
+
+    pub message: Message,
+
+    #[serde(default)]
+    pub locations: Vec<Location>,
+}
+
+impl SarifResult {
+    /// Returns the rule ID of the result, if any.
+    pub fn rule_id(&self) -> Option<&str> {
+        self.rule_id
+            .as_ref()
+            .or_else(|| self.rule.as_ref().and_then(|r| r.id.as_ref()))
+            .map(String::as_str)
+    }
+
+    /// Looks up the rule of this result in the tool's rules.
+    fn find_rule<'a>(
+        &self,
+        tool: &'a ToolComponent,
+    ) -> Option<&'a ReportingDescriptor> {
+        let index = self
+            .rule_index
+            .or_else(|| self.rule.as_ref().and_then(|r| r.index));
+
+        if let Some(rule) = index.and_then(|i| tool.rules.get(i)) {
+            return Some(rule);
+        }
+
+        let id = self.rule_id()?;
+        tool.rules.iter().find(|r| r.id == id)
+    }
+
+    /// Returns the effective level of the result, following the defaulting
+    /// rules of the SARIF specification.
+    fn effective_level(&self, rule: Option<&ReportingDescriptor>) -> Level {
+        match self.kind {
+            None | Some(ResultKind::Fail) => {}
+            Some(_) => return Level::None,
+        }
+
+        self.level
+            .or_else(|| {
+                rule.and_then(|r| r.default_configuration.as_ref())
+                    .and_then(|c| c.level)
+            })
+            .unwrap_or(Level::Warning)
+    }
+
+    /// Converts the result into an annotation.
+    ///
+    /// Returns `None` if the result doesn't refer to a file within the
+    /// workspace or is a passing result. Results that refer to a file without
+    /// a region are placed on the first line.
+    fn to_annotation(
+        &self,
+        tool: &ToolComponent,
+        workspace_root: &Path,
+    ) -> Option<Annotation> {
+        match self.kind {
+            Some(ResultKind::Pass) | Some(ResultKind::NotApplicable) => {
+                return None
+            }
+            _ => {}
+        }
+
+        let location = self
+            .locations
+            .iter()
+            .filter_map(|l| l.physical_location.as_ref())
+            .find(|l| l.artifact_location.is_some())?;
+
+        let path = location.artifact_location.as_ref()?.path(workspace_root)?;
+
+        let region = location.region.as_ref();
+        let start_line = region.and_then(|r| r.start_line).unwrap_or(1).max(1);
+        let end_line = region
+            .and_then(|r| r.end_line)
+            .unwrap_or(start_line)
+            .max(start_line);
+
+        // Columns are only supported by the API when the annotation is on a
+        // single line.
+        let (start_column, end_column) = match region {
+            Some(r) if start_line == end_line => (r.start_column, r.end_column),
+            _ => (None, None),
+        };
+
+        let rule = self.find_rule(tool);
+
+        let message = self
+            .message
+            .format()
+            .or_else(|| rule.and_then(|r| r.description()).map(String::from))
+            .or_else(|| self.rule_id().map(String::from))
+            .unwrap_or_default();
+
+        Some(Annotation {
+            path,
+            start_line,
+            end_line,
+            start_column,
+            end_column,
+            annotation_level: self.effective_level(rule).into(),
+            message: truncate_annotation_text(message),
+            title: self.rule_id().map(String::from),
+            raw_details: rule
+                .and_then(|r| r.help_text())
+                .map(truncate_annotation_text),
+        })
+    }
+}
+
+/// Decodes `%XX` escapes in a URI path. Returns `None` if the result is not
+/// valid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(b) = s
+                .get(i + 1..i + 3)
+                .filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+            {
+                decoded.push(b);
+                i += 3;
+                continue;
+            }
+        }
+
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Per-rule statistics used to build the summary table.
+#[derive(Default)]
+struct RuleCount<'a> {
+    description: Option<&'a str>,
+    level: Option<Level>,
+    count: usize,
+}
+
+impl Log {
+    /// Converts the log into check run output.
+    ///
+    /// Every result with a file location becomes an annotation. Relative URIs
+    /// are assumed to be relative to the repository root, while absolute
+    /// `file://` URIs are made relative to `workspace_root`. The summary
+    /// contains a table with the number of results for each rule.
+    /// The annotations are not limited to `MAX_ANNOTATIONS`; use
+    /// `Output::into_batches` to split them up.
+    pub fn to_output(&self, workspace_root: &Path) -> Output {
+        let mut annotations = Vec::new();
+        let mut rules: BTreeMap<&str, RuleCount> = BTreeMap::new();
+        let mut total = 0;
+
+        for run in &self.runs {
+            let tool = &run.tool.driver;
+
+            for result in &run.results {
+                match result.kind {
+                    Some(ResultKind::Pass)
+                    | Some(ResultKind::NotApplicable) => continue,
+                    _ => {}
+                }
+
+                total += 1;
+
+                let rule = result.find_rule(tool);
+                let level = result.effective_level(rule);
+
+                let count = rules
+                    .entry(result.rule_id().unwrap_or("(none)"))
+                    .or_default();
+                count.count += 1;
+                count.level = count.level.max(Some(level));
+                if count.description.is_none() {
+                    count.description = rule.and_then(|r| r.description());
+                }
+
+                if let Some(annotation) =
+                    result.to_annotation(tool, workspace_root)
+                {
+                    annotations.push(annotation);
+                }
+            }
+        }
+
+        let tools = self
+            .runs
+            .iter()
+            .map(|r| r.tool.driver.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let title = match total {
+            0 => "No problems found".to_string(),
+            1 => "1 problem found".to_string(),
+            n => format!("{} problems found", n),
+        };
+
+        let mut summary = Markdown::new();
+
+        if !tools.is_empty() {
+            summary.paragraph(&format!("Analyzed by {}.", tools));
+        }
+
+        if !rules.is_empty() {
+            let mut rules: Vec<_> = rules.into_iter().collect();
+            rules.sort_by_key(|(_, count)| Reverse(count.count));
+
+            let mut table =
+                Table::new(["Rule", "Level", "Description", "Count"])
+                    .align(3, Alignment::Right);
+
+            for (id, count) in rules {
+                let level = match count.level {
+                    Some(Level::Error) => "error",
+                    Some(Level::Warning) => "warning",
+                    Some(Level::Note) => "note",
+                    Some(Level::None) | None => "none",
+                };
+
+                table.raw_row(vec![
+                    markdown::code(id).replace('|', "\\|"),
+                    level.to_string(),
+                    markdown::escape_cell(count.description.unwrap_or("")),
+                    count.count.to_string(),
+                ]);
+            }
+
+            summary.table(&table);
+        }
+
+        let summary = summary.finish();
+
+        Output {
+            title,
+            summary,
+            text: None,
+            annotations: Some(annotations),
+            images: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MAX_ANNOTATION_MESSAGE_SIZE;
+
+    fn message(text: &str, arguments: &[&str]) -> Option<String> {
+        Message {
+            text: Some(text.to_string()),
+            markdown: None,
+            arguments: arguments.iter().map(|a| a.to_string()).collect(),
+        }
+        .format()
+    }
+
+    #[test]
+    fn message_arguments() {
+        assert_eq!(
+            message("{0} is unused in {1}.", &["x", "f"]).unwrap(),
+            "x is unused in f."
+        );
+        assert_eq!(message("{1}{0}{1}", &["a", "b"]).unwrap(), "bab");
+
+        // Missing or malformed placeholders are left as is.
+        assert_eq!(
+            message("{2} {} {+0} {a}", &["a"]).unwrap(),
+            "{2} {} {+0} {a}"
+        );
+        assert_eq!(message("{0", &["a"]).unwrap(), "{0");
+    }
+
+    #[test]
+    fn message_escapes() {
+        assert_eq!(message("{{0}}", &["a"]).unwrap(), "{0}");
+        assert_eq!(message("{{{0}}}", &["a"]).unwrap(), "{a}");
+        assert_eq!(message("fn() {{}}", &[]).unwrap(), "fn() {}");
+        assert_eq!(message("}", &[]).unwrap(), "}");
+    }
+
+    #[test]
+    fn percent_decoding() {
+        assert_eq!(percent_decode("a%20b%2Fc").unwrap(), "a b/c");
+        assert_eq!(percent_decode("%C3%A9").unwrap(), "é");
+
+        // Invalid escapes are left as is.
+        assert_eq!(percent_decode("%+1%-1%2").unwrap(), "%+1%-1%2");
+        assert_eq!(percent_decode("%zz%").unwrap(), "%zz%");
+        assert_eq!(percent_decode("%é").unwrap(), "%é");
+
+        assert_eq!(percent_decode("%FF"), None);
+    }
+
+    #[test]
+    fn artifact_paths() {
+        let path = |uri: &str| {
+            ArtifactLocation {
+                uri: Some(uri.to_string()),
+                uri_base_id: None,
+            }
+            .path(Path::new("/work"))
+        };
+
+        assert_eq!(path("src/main.rs").unwrap(), "src/main.rs");
+        assert_eq!(path("./src/a%20b.rs").unwrap(), "src/a b.rs");
+        assert_eq!(path("file:///work/src/main.rs").unwrap(), "src/main.rs");
+        assert_eq!(path("file:///other/main.rs"), None);
+        assert_eq!(path("../main.rs"), None);
+    }
+
+    #[test]
+    fn to_output() {
+        let log: Log = serde_json::from_str(
+            r#"{
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "lint",
+                            "rules": [{
+                                "id": "no-unused",
+                                "shortDescription": {"text": "Unused"},
+                                "helpUri": "https://example.com/no-unused",
+                                "defaultConfiguration": {"level": "error"}
+                            }]
+                        }
+                    },
+                    "results": [
+                        {
+                            "ruleId": "no-unused",
+                            "message": {
+                                "text": "{0} is unused",
+                                "arguments": ["x"]
+                            },
+                            "locations": [{
+                                "physicalLocation": {
+                                    "artifactLocation": {"uri": "src/lib.rs"},
+                                    "region": {
+                                        "startLine": 3,
+                                        "startColumn": 5,
+                                        "endColumn": 6
+                                    }
+                                }
+                            }]
+                        },
+                        {
+                            "ruleId": "other",
+                            "level": "note",
+                            "message": {"text": "no location"}
+                        },
+                        {
+                            "ruleId": "no-unused",
+                            "kind": "pass",
+                            "message": {"text": "fine"}
+                        }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let output = log.to_output(Path::new("/work"));
+        assert_eq!(output.title, "2 problems found");
+        assert!(output.summary.starts_with("Analyzed by lint."));
+
+        let annotations = output.annotations.unwrap();
+        assert_eq!(annotations.len(), 1);
+
+        let annotation = &annotations[0];
+        assert_eq!(annotation.path, "src/lib.rs");
+        assert_eq!((annotation.start_line, annotation.end_line), (3, 3));
+        assert_eq!(
+            (annotation.start_column, annotation.end_column),
+            (Some(5), Some(6))
+        );
+        assert_eq!(annotation.annotation_level, AnnotationLevel::Failure);
+        assert_eq!(annotation.message, "x is unused");
+        assert_eq!(annotation.title.as_deref(), Some("no-unused"));
+        assert_eq!(
+            annotation.raw_details.as_deref(),
+            Some("https://example.com/no-unused")
+        );
+    }
+
+    #[test]
+    fn large_results_are_truncated() {
+        let log = serde_json::json!({
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "lint",
+                        "rules": [{
+                            "id": "verbose",
+                            "help": {
+                                "text": format!("a{}", "é".repeat(40_000))
+                            }
+                        }]
+                    }
+                },
+                "results": [{
+                    "ruleId": "verbose",
+                    "message": {
+                        "text": "{0}",
+                        "arguments": ["x".repeat(100_000)]
+                    },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {"uri": "src/lib.rs"}
+                        }
+                    }]
+                }]
+            }]
+        });
+        let log: Log = serde_json::from_value(log).unwrap();
+
+        let output = log.to_output(Path::new("/work"));
+        let annotation = &output.annotations.unwrap()[0];
+
+        assert_eq!(annotation.message.len(), MAX_ANNOTATION_MESSAGE_SIZE);
+
+        // The help text is cut on a UTF-8 boundary.
+        let details = annotation.raw_details.as_deref().unwrap();
+        assert_eq!(details.len(), MAX_ANNOTATION_MESSAGE_SIZE - 1);
+        assert!(details.ends_with('é'));
+    }
+}
@@ -0,0 +1,506 @@
+// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! JSON Schemas for webhook payloads.
+//!
+//! When the `schemars` feature is enabled, every type implements
+//! `schemars::JsonSchema`. Most types derive it. The types implemented here
+//! have hand-written `Serialize` and `Deserialize` implementations, so their
+//! schemas are written by hand as well.
+//!
+//! To get the schema for a webhook payload, use [`payload`]:
+//!
+//! ```
+//! use github_types::{schema, EventType};
+//!
+//! let schema = schema::payload(EventType::Push).unwrap();
+//! assert!(schema.definitions.contains_key("Oid"));
+//! ```
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, RootSchema, Schema, SchemaObject, StringValidation,
+    SubschemaValidation,
+};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use crate::{
+    CheckRunEvent, CheckSuiteEvent, CommitCommentEvent, CreateEvent, DateTime,
+    DeleteEvent, DeployKeyEvent, EventType, GitHubAppAuthorizationEvent,
+    GollumEvent, InstallationEvent, InstallationRepositoriesEvent,
+    IntegrationInstallationEvent, IntegrationInstallationRepositoriesEvent,
+    IssueCommentEvent, IssuesEvent, LabelEvent, MetaEvent, Oid, PackageEvent,
+    PackageType, PingEvent, PullRequestEvent, PullRequestReviewCommentEvent,
+    PullRequestReviewEvent, PullRequestReviewThreadEvent, PushEvent,
+    RegistryPackageEvent, RepositoryDispatchEvent, RepositoryEvent,
+    RepositoryRulesetEvent, StarEvent, WatchEvent,
+};
+
+/// Every event type, in the order they are declared. The tests check that
+/// this is complete.
+const EVENT_TYPES: &[EventType] = &[
+    EventType::Wildcard,
+    EventType::Ping,
+    EventType::CheckRun,
+    EventType::CheckSuite,
+    EventType::CommitComment,
+    EventType::ContentReference,
+    EventType::Create,
+    EventType::Delete,
+    EventType::DeployKey,
+    EventType::Deployment,
+    EventType::DeploymentStatus,
+    EventType::Fork,
+    EventType::GitHubAppAuthorization,
+    EventType::Gollum,
+    EventType::Installation,
+    EventType::IntegrationInstallation,
+    EventType::InstallationRepositories,
+    EventType::IntegrationInstallationRepositories,
+    EventType::IssueComment,
+    EventType::Issues,
+    EventType::Label,
+    EventType::MarketplacePurchase,
+    EventType::Member,
+    EventType::Membership,
+    EventType::Meta,
+    EventType::Milestone,
+    EventType::Organization,
+    EventType::OrgBlock,
+    EventType::Package,
+    EventType::PageBuild,
+    EventType::ProjectCard,
+    EventType::ProjectColumn,
+    EventType::Project,
+    EventType::Public,
+    EventType::PullRequest,
+    EventType::PullRequestReviewComment,
+    EventType::PullRequestReview,
+    EventType::PullRequestReviewThread,
+    EventType::Push,
+    EventType::Release,
+    EventType::RegistryPackage,
+    EventType::Repository,
+    EventType::RepositoryDispatch,
+    EventType::RepositoryImport,
+    EventType::RepositoryRuleset,
+    EventType::RepositoryVulnerabilityAlert,
+    EventType::SecurityAdvisory,
+    EventType::Star,
+    EventType::Status,
+    EventType::Team,
+    EventType::TeamAdd,
+    EventType::Watch,
+];
+
+/// Every package type, in the order they are declared. The tests check that
+/// this is complete.
+const PACKAGE_TYPES: &[PackageType] = &[
+    PackageType::Npm,
+    PackageType::Maven,
+    PackageType::Rubygems,
+    PackageType::Docker,
+    PackageType::Nuget,
+    PackageType::Container,
+];
+
+/// Returns the schema for the payload of a webhook event, or `None` if the
+/// event type isn't supported.
+///
+/// An event type is supported if `Event::from_json` can parse its payload.
+/// The schema describes the payload as it is sent by GitHub (i.e., the JSON
+/// accepted by the corresponding event struct), with the types it refers to
+/// in `definitions`.
+pub fn payload(event_type: EventType) -> Option<RootSchema> {
+    let gen = SchemaGenerator::default();
+
+    let schema = match event_type {
+        EventType::Ping => gen.into_root_schema_for::<PingEvent>(),
+        EventType::CheckRun => gen.into_root_schema_for::<CheckRunEvent>(),
+        EventType::CheckSuite => gen.into_root_schema_for::<CheckSuiteEvent>(),
+        EventType::CommitComment => {
+            gen.into_root_schema_for::<CommitCommentEvent>()
+        }
+        EventType::Create => gen.into_root_schema_for::<CreateEvent>(),
+        EventType::Delete => gen.into_root_schema_for::<DeleteEvent>(),
+        EventType::DeployKey => gen.into_root_schema_for::<DeployKeyEvent>(),
+        EventType::GitHubAppAuthorization => {
+            gen.into_root_schema_for::<GitHubAppAuthorizationEvent>()
+        }
+        EventType::Gollum => gen.into_root_schema_for::<GollumEvent>(),
+        EventType::Installation => {
+            gen.into_root_schema_for::<InstallationEvent>()
+        }
+        EventType::InstallationRepositories => {
+            gen.into_root_schema_for::<InstallationRepositoriesEvent>()
+        }
+        EventType::IntegrationInstallation => {
+            gen.into_root_schema_for::<IntegrationInstallationEvent>()
+        }
+        EventType::IntegrationInstallationRepositories => gen
+            .into_root_schema_for::<IntegrationInstallationRepositoriesEvent>(
+            ),
+        EventType::IssueComment => {
+            gen.into_root_schema_for::<IssueCommentEvent>()
+        }
+        EventType::Issues => gen.into_root_schema_for::<IssuesEvent>(),
+        EventType::Label => gen.into_root_schema_for::<LabelEvent>(),
+        EventType::Meta => gen.into_root_schema_for::<MetaEvent>(),
+        EventType::Package => gen.into_root_schema_for::<PackageEvent>(),
+        EventType::PullRequest => {
+            gen.into_root_schema_for::<PullRequestEvent>()
+        }
+        EventType::PullRequestReview => {
+            gen.into_root_schema_for::<PullRequestReviewEvent>()
+        }
+        EventType::PullRequestReviewComment => {
+            gen.into_root_schema_for::<PullRequestReviewCommentEvent>()
+        }
+        EventType::PullRequestReviewThread => {
+            gen.into_root_schema_for::<PullRequestReviewThreadEvent>()
+        }
+        EventType::Push => gen.into_root_schema_for::<PushEvent>(),
+        EventType::RegistryPackage => {
+            gen.into_root_schema_for::<RegistryPackageEvent>()
+        }
+        EventType::Repository => gen.into_root_schema_for::<RepositoryEvent>(),
+        EventType::RepositoryDispatch => {
+            gen.into_root_schema_for::<RepositoryDispatchEvent>()
+        }
+        EventType::RepositoryRuleset => {
+            gen.into_root_schema_for::<RepositoryRulesetEvent>()
+        }
+        EventType::Star => gen.into_root_schema_for::<StarEvent>(),
+        EventType::Watch => gen.into_root_schema_for::<WatchEvent>(),
+        _ => return None,
+    };
+
+    Some(schema)
+}
+
+/// Returns the schemas for the payloads of all supported webhook events, in
+/// the order the event types are declared.
+///
+/// See [`payload`].
+pub fn payloads() -> impl Iterator<Item = (EventType, RootSchema)> {
+    EVENT_TYPES
+        .iter()
+        .filter_map(|&event_type| Some((event_type, payload(event_type)?)))
+}
+
+/// Returns a schema for a string that is one of the given names.
+fn names<I>(description: &str, names: I) -> Schema
+where
+    I: IntoIterator,
+    I::Item: Into<Value>,
+{
+    SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            ..Default::default()
+        })),
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(names.into_iter().map(Into::into).collect()),
+        ..Default::default()
+    }
+    .into()
+}
+
+impl JsonSchema for Oid {
+    fn schema_name() -> String {
+        "Oid".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "A Git object ID (i.e., a SHA1).".to_string(),
+                ),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                max_length: Some(40),
+                min_length: Some(40),
+                pattern: Some("^[0-9a-fA-F]{40}$".to_string()),
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl JsonSchema for DateTime {
+    fn schema_name() -> String {
+        "DateTime".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        // GitHub usually sends RFC 3339 strings, but some payloads (e.g.,
+        // `push`) use seconds since the unix epoch instead.
+        let rfc3339 = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("date-time".to_string()),
+            ..Default::default()
+        };
+
+        let timestamp = SchemaObject {
+            instance_type: Some(InstanceType::Integer.into()),
+            format: Some("int64".to_string()),
+            ..Default::default()
+        };
+
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "A date time string or seconds since the unix epoch."
+                        .to_string(),
+                ),
+                ..Default::default()
+            })),
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![rfc3339.into(), timestamp.into()]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl JsonSchema for EventType {
+    fn schema_name() -> String {
+        "EventType".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        names(
+            "The name of a webhook event.",
+            EVENT_TYPES.iter().map(|t| t.name()),
+        )
+    }
+}
+
+impl JsonSchema for PackageType {
+    fn schema_name() -> String {
+        "PackageType".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        // GitHub sends some package types in upper case (e.g., `CONTAINER`).
+        names(
+            "The type of a package.",
+            PACKAGE_TYPES
+                .iter()
+                .map(|t| t.name().to_string())
+                .chain(PACKAGE_TYPES.iter().map(|t| t.name().to_uppercase())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fixtures::FIXTURES;
+    use crate::Event;
+
+    /// Returns the event type declared after the given one. There is no
+    /// catch-all arm, so this stops compiling when a variant is added to
+    /// `EventType`.
+    fn next_event_type(event_type: EventType) -> Option<EventType> {
+        use EventType::*;
+
+        Some(match event_type {
+            Wildcard => Ping,
+            Ping => CheckRun,
+            CheckRun => CheckSuite,
+            CheckSuite => CommitComment,
+            CommitComment => ContentReference,
+            ContentReference => Create,
+            Create => Delete,
+            Delete => DeployKey,
+            DeployKey => Deployment,
+            Deployment => DeploymentStatus,
+            DeploymentStatus => Fork,
+            Fork => GitHubAppAuthorization,
+            GitHubAppAuthorization => Gollum,
+            Gollum => Installation,
+            Installation => IntegrationInstallation,
+            IntegrationInstallation => InstallationRepositories,
+            InstallationRepositories => IntegrationInstallationRepositories,
+            IntegrationInstallationRepositories => IssueComment,
+            IssueComment => Issues,
+            Issues => Label,
+            Label => MarketplacePurchase,
+            MarketplacePurchase => Member,
+            Member => Membership,
+            Membership => Meta,
+            Meta => Milestone,
+            Milestone => Organization,
+            Organization => OrgBlock,
+            OrgBlock => Package,
+            Package => PageBuild,
+            PageBuild => ProjectCard,
+            ProjectCard => ProjectColumn,
+            ProjectColumn => Project,
+            Project => Public,
+            Public => PullRequest,
+            PullRequest => PullRequestReviewComment,
+            PullRequestReviewComment => PullRequestReview,
+            PullRequestReview => PullRequestReviewThread,
+            PullRequestReviewThread => Push,
+            Push => Release,
+            Release => RegistryPackage,
+            RegistryPackage => Repository,
+            Repository => RepositoryDispatch,
+            RepositoryDispatch => RepositoryImport,
+            RepositoryImport => RepositoryRuleset,
+            RepositoryRuleset => RepositoryVulnerabilityAlert,
+            RepositoryVulnerabilityAlert => SecurityAdvisory,
+            SecurityAdvisory => Star,
+            Star => Status,
+            Status => Team,
+            Team => TeamAdd,
+            TeamAdd => Watch,
+            Watch => return None,
+        })
+    }
+
+    /// Like `next_event_type`, but for `PackageType`.
+    fn next_package_type(package_type: PackageType) -> Option<PackageType> {
+        use PackageType::*;
+
+        Some(match package_type {
+            Npm => Maven,
+            Maven => Rubygems,
+            Rubygems => Docker,
+            Docker => Nuget,
+            Nuget => Container,
+            Container => return None,
+        })
+    }
+
+    #[test]
+    fn event_types_are_complete() {
+        let all: Vec<_> =
+            std::iter::successors(Some(EventType::Wildcard), |&t| {
+                next_event_type(t)
+            })
+            .collect();
+
+        // `EventType` orders its variants by declaration.
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(all, EVENT_TYPES);
+    }
+
+    #[test]
+    fn package_types_are_complete() {
+        let all: Vec<_> = std::iter::successors(Some(PackageType::Npm), |&t| {
+            next_package_type(t)
+        })
+        .collect();
+
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(all, PACKAGE_TYPES);
+    }
+
+    #[test]
+    fn every_parsable_event_has_a_schema() {
+        for &event_type in EVENT_TYPES {
+            // Unsupported event types are rejected before the payload is
+            // looked at.
+            let supported = match Event::from_json(event_type, "null") {
+                Ok(_) => true,
+                Err(err) => !err.to_string().starts_with("unsupported"),
+            };
+
+            assert_eq!(
+                payload(event_type).is_some(),
+                supported,
+                "{}",
+                event_type
+            );
+        }
+    }
+
+    #[test]
+    fn fixtures_match_their_schema() {
+        for fixture in FIXTURES {
+            let schema =
+                serde_json::to_value(payload(fixture.event_type).unwrap())
+                    .unwrap();
+            let validator = jsonschema::validator_for(&schema).unwrap();
+            assert!(!validator.is_valid(&Value::Null));
+
+            let value: Value = serde_json::from_str(fixture.payload).unwrap();
+            let errors: Vec<_> = validator
+                .iter_errors(&value)
+                .map(|e| format!("{} at {}", e, e.instance_path))
+                .collect();
+
+            assert!(
+                errors.is_empty(),
+                "{} doesn't match its schema:\n{}",
+                fixture.name(),
+                errors.join("\n")
+            );
+        }
+    }
+
+    #[test]
+    fn every_fixture_has_a_schema() {
+        for fixture in FIXTURES {
+            let schema = payload(fixture.event_type)
+                .unwrap_or_else(|| panic!("no schema for {}", fixture.name()));
+
+            let value: Value = serde_json::from_str(fixture.payload).unwrap();
+            let object = value.as_object().unwrap();
+
+            for field in &schema.schema.object.as_ref().unwrap().required {
+                assert!(
+                    object.contains_key(field),
+                    "{} is missing required field `{}`",
+                    fixture.name(),
+                    field
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn payloads_are_supported_events() {
+        for (event_type, _) in payloads() {
+            assert!(EVENT_TYPES.contains(&event_type));
+            assert!(event_type != EventType::Wildcard);
+        }
+
+        assert_eq!(payloads().count(), 29);
+    }
+
+    #[test]
+    fn event_type_names_parse() {
+        for event_type in EVENT_TYPES {
+            assert_eq!(event_type.name().parse(), Ok(*event_type));
+        }
+    }
+}
@@ -18,13 +18,19 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::{TeamId, UserId};
 
 /// Information about a user.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct User {
     pub login: String,
-    pub id: u64,
+    pub id: UserId,
     pub avatar_url: String,
     pub gravatar_id: String,
     pub url: String,
@@ -42,10 +48,14 @@ pub struct User {
 }
 
 /// Information about the current authenticated user.
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct AuthenticatedUser {
     pub login: String,
-    pub id: u64,
+    pub id: UserId,
     pub avatar_url: String,
     pub gravatar_id: String,
     pub url: String,
@@ -70,3 +80,23 @@ pub struct AuthenticatedUser {
     pub hireable: Option<bool>,
     pub bio: Option<String>,
 }
+
+/// Information about a team.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Team {
+    pub id: TeamId,
+    pub node_id: Option<String>,
+    pub url: String,
+    pub html_url: String,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub privacy: Option<String>,
+    pub permission: String,
+    pub members_url: String,
+    pub repositories_url: String,
+}
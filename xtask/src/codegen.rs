@@ -0,0 +1,991 @@
+//! Generates Rust types from the schemas of an OpenAPI description.
+//!
+//! Only the schemas in `overrides::ROOTS` and the schemas they refer to are
+//! generated. Schemas that have a hand-written equivalent are mapped onto it
+//! instead.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use serde_json::{Map, Value};
+
+use crate::overrides;
+
+/// Header of the generated file.
+const HEADER: &str = r#"// Copyright (c) 2019 Jason White
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Types generated from GitHub's OpenAPI description.
+//!
+//! This file is generated by `cargo xtask codegen`. Do not edit it by hand.
+//! See `openapi/README.md`.
+//!
+//! Free-form JSON is represented as `ClientPayload` so that it can be ordered
+//! and hashed. Types with floating point fields only derive `PartialEq`, since
+//! floats have no total order.
+"#;
+
+/// Identifiers that can't be used as field names.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate",
+    "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Names that generated types must not use.
+const RESERVED: &[&str] = &[
+    "Box",
+    "BTreeMap",
+    "ClientPayload",
+    "DateTime",
+    "Oid",
+    "Option",
+    "String",
+    "Vec",
+];
+
+/// Generates the types for the given root schemas.
+pub fn generate(spec: &Value, roots: &[&str]) -> Result<String, String> {
+    let schemas = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .ok_or("missing `components.schemas`")?;
+
+    let mut generator = Generator {
+        schemas,
+        types: BTreeMap::new(),
+        names: BTreeMap::new(),
+        in_progress: BTreeSet::new(),
+        reserved: None,
+        imports: BTreeSet::new(),
+    };
+
+    for root in roots {
+        generator.schema(root)?;
+    }
+
+    Ok(generator.finish())
+}
+
+struct Generator<'a> {
+    schemas: &'a Map<String, Value>,
+
+    /// Generated types, keyed by name.
+    types: BTreeMap<String, TypeDef>,
+
+    /// Maps schema names onto the types generated for them.
+    names: BTreeMap<String, String>,
+
+    /// Types that are still being generated. References to these must be
+    /// boxed, since the type would otherwise be infinitely large.
+    in_progress: BTreeSet<String>,
+
+    /// The name reserved for the schema that is being generated.
+    reserved: Option<String>,
+
+    /// Items from the crate that the generated types refer to.
+    imports: BTreeSet<&'static str>,
+}
+
+struct TypeDef {
+    doc: Option<String>,
+    kind: Kind,
+}
+
+enum Kind {
+    Struct(Vec<Field>),
+    Enum(Vec<Variant>),
+}
+
+struct Field {
+    ident: String,
+    wire: String,
+    ty: String,
+    doc: Option<String>,
+}
+
+struct Variant {
+    ident: String,
+    wire: String,
+}
+
+impl<'a> Generator<'a> {
+    /// Returns the type for the named schema, generating it if needed, and
+    /// whether it is nullable.
+    fn schema(&mut self, name: &str) -> Result<(String, bool), String> {
+        let schema = self
+            .schemas
+            .get(name)
+            .ok_or_else(|| format!("unknown schema `{}`", name))?;
+
+        let nullable = is_nullable(schema);
+
+        if let Some(ty) = overrides::type_for(name) {
+            self.imports.insert(ty);
+            return Ok((ty.to_string(), nullable));
+        }
+
+        if let Some(ty) = self.names.get(name) {
+            let ty = if self.in_progress.contains(ty) {
+                format!("Box<{}>", ty)
+            } else {
+                ty.clone()
+            };
+
+            return Ok((ty, nullable));
+        }
+
+        let type_name = self.unique_name(&pascal_case(name));
+        self.names.insert(name.to_string(), type_name.clone());
+        self.reserved = Some(type_name.clone());
+
+        let (ty, nullable) = self.type_of(name, &type_name, schema)?;
+
+        // Schemas that aren't objects or enums are aliases for another type.
+        self.names.insert(name.to_string(), ty.clone());
+
+        Ok((ty, nullable))
+    }
+
+    /// Returns the type for a schema and whether it is nullable. Any inline
+    /// objects or enums are generated with the name `context`.
+    ///
+    /// `owner` is the name of the schema this is a part of, which is used to
+    /// look up overrides.
+    fn type_of(
+        &mut self,
+        owner: &str,
+        context: &str,
+        schema: &Value,
+    ) -> Result<(String, bool), String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name =
+                reference.strip_prefix("#/components/schemas/").ok_or_else(
+                    || format!("unsupported reference `{}`", reference),
+                )?;
+
+            return self.schema(name);
+        }
+
+        let mut nullable = is_nullable(schema);
+
+        for key in &["anyOf", "oneOf"] {
+            if let Some(variants) = schema.get(*key).and_then(Value::as_array) {
+                let non_null: Vec<_> =
+                    variants.iter().filter(|v| !is_null(v)).collect();
+
+                nullable |= non_null.len() < variants.len();
+
+                if let [variant] = non_null.as_slice() {
+                    let (ty, n) = self.type_of(owner, context, variant)?;
+                    return Ok((ty, nullable || n));
+                }
+
+                return Ok((self.json(), nullable));
+            }
+        }
+
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            // A single part is commonly used to make a reference nullable.
+            if let [part] = parts.as_slice() {
+                let (ty, n) = self.type_of(owner, context, part)?;
+                return Ok((ty, nullable || n));
+            }
+
+            let merged = self.merge(schema, parts)?;
+            let ty = self.object(owner, context, &merged)?;
+            return Ok((ty, nullable));
+        }
+
+        let ty = match primary_type(schema) {
+            Some("string") => {
+                if let Some(values) =
+                    schema.get("enum").and_then(Value::as_array)
+                {
+                    nullable |= values.iter().any(Value::is_null);
+                    self.enumeration(context, schema, values)
+                } else if format(schema) == Some("date-time") {
+                    self.imports.insert("DateTime");
+                    "DateTime".to_string()
+                } else {
+                    "String".to_string()
+                }
+            }
+            Some("integer") => {
+                let signed = schema
+                    .get("minimum")
+                    .and_then(Value::as_i64)
+                    .is_some_and(|min| min < 0);
+
+                if signed { "i64" } else { "u64" }.to_string()
+            }
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => {
+                let items = schema.get("items").unwrap_or(&Value::Null);
+                let context = format!("{}Item", context);
+                let (ty, _) = self.type_of(owner, &context, items)?;
+                format!("Vec<{}>", ty)
+            }
+            Some("object") | None if has_properties(schema) => {
+                self.object(owner, context, schema)?
+            }
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => {
+                    let context = format!("{}Value", context);
+                    let (ty, _) = self.type_of(owner, &context, values)?;
+                    format!("BTreeMap<String, {}>", ty)
+                }
+                _ => self.json(),
+            },
+            _ => self.json(),
+        };
+
+        Ok((ty, nullable))
+    }
+
+    /// Returns the type used for free-form JSON. `ClientPayload` is used
+    /// instead of `serde_json::Value` since it can be ordered and hashed.
+    fn json(&mut self) -> String {
+        self.imports.insert("ClientPayload");
+        "ClientPayload".to_string()
+    }
+
+    /// Merges the parts of an `allOf` schema into a single object schema.
+    fn merge(&self, schema: &Value, parts: &[Value]) -> Result<Value, String> {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+
+        for part in parts {
+            let part = self.resolve(part)?;
+
+            if let Some(p) = part.get("properties").and_then(Value::as_object) {
+                for (key, value) in p {
+                    properties.insert(key.clone(), value.clone());
+                }
+            }
+
+            if let Some(r) = part.get("required").and_then(Value::as_array) {
+                required.extend(r.iter().cloned());
+            }
+        }
+
+        let mut merged = Map::new();
+        merged.insert("type".into(), "object".into());
+        merged.insert("properties".into(), Value::Object(properties));
+        merged.insert("required".into(), Value::Array(required));
+
+        if let Some(description) = schema.get("description") {
+            merged.insert("description".into(), description.clone());
+        }
+
+        Ok(Value::Object(merged))
+    }
+
+    /// Follows a reference, if any.
+    fn resolve<'b>(&'b self, schema: &'b Value) -> Result<&'b Value, String> {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => reference
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| self.schemas.get(name))
+                .ok_or_else(|| format!("unknown reference `{}`", reference)),
+            None => Ok(schema),
+        }
+    }
+
+    /// Generates a struct for an object schema.
+    fn object(
+        &mut self,
+        owner: &str,
+        name: &str,
+        schema: &Value,
+    ) -> Result<String, String> {
+        let name = self.claim(name);
+
+        self.in_progress.insert(name.clone());
+
+        let required: BTreeSet<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        let empty = Map::new();
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .unwrap_or(&empty);
+
+        let mut fields = Vec::new();
+
+        for (wire, property) in properties {
+            let (ty, nullable) =
+                if let Some(ty) = overrides::property_for(owner, wire) {
+                    self.imports.insert(ty);
+                    (ty.to_string(), is_nullable(property))
+                } else if overrides::OIDS.contains(&wire.as_str())
+                    && primary_type(property) == Some("string")
+                {
+                    self.imports.insert("Oid");
+                    ("Oid".to_string(), is_nullable(property))
+                } else {
+                    let context = format!("{}{}", name, pascal_case(wire));
+                    self.type_of(owner, &context, property)?
+                };
+
+            let ty = if nullable || !required.contains(wire.as_str()) {
+                format!("Option<{}>", ty)
+            } else {
+                ty
+            };
+
+            fields.push(Field {
+                ident: field_ident(wire, &name),
+                wire: wire.clone(),
+                ty,
+                doc: description(property),
+            });
+        }
+
+        self.in_progress.remove(&name);
+        self.types.insert(
+            name.clone(),
+            TypeDef {
+                doc: description(schema),
+                kind: Kind::Struct(fields),
+            },
+        );
+
+        Ok(name)
+    }
+
+    /// Generates an enum for a string schema with a fixed set of values.
+    fn enumeration(
+        &mut self,
+        name: &str,
+        schema: &Value,
+        values: &[Value],
+    ) -> String {
+        let name = self.claim(name);
+
+        let mut variants: Vec<Variant> = Vec::new();
+
+        for wire in values.iter().filter_map(Value::as_str) {
+            let mut ident = variant_ident(wire);
+
+            while variants.iter().any(|v| v.ident == ident) {
+                ident.push('_');
+            }
+
+            variants.push(Variant {
+                ident,
+                wire: wire.to_string(),
+            });
+        }
+
+        self.types.insert(
+            name.clone(),
+            TypeDef {
+                doc: description(schema),
+                kind: Kind::Enum(variants),
+            },
+        );
+
+        name
+    }
+
+    /// Returns the name for a new type. This is the name reserved for the
+    /// schema being generated, if it matches, or a new unique name.
+    fn claim(&mut self, name: &str) -> String {
+        if self.reserved.as_deref() == Some(name) {
+            self.reserved.take().unwrap()
+        } else {
+            self.unique_name(name)
+        }
+    }
+
+    /// Returns a name that isn't used by any other type.
+    fn unique_name(&self, name: &str) -> String {
+        let taken = |n: &str| {
+            RESERVED.contains(&n)
+                || overrides::TYPES.iter().any(|(_, t)| *t == n)
+                || self.types.contains_key(n)
+                || self.in_progress.contains(n)
+                || self.names.values().any(|v| v == n)
+        };
+
+        if !taken(name) {
+            return name.to_string();
+        }
+
+        (2..)
+            .map(|i| format!("{}{}", name, i))
+            .find(|n| !taken(n))
+            .unwrap()
+    }
+
+    fn finish(self) -> String {
+        let mut out = String::from(HEADER);
+
+        if self.types.is_empty() {
+            return out;
+        }
+
+        let uses_map = self.types.values().any(|t| match &t.kind {
+            Kind::Struct(fields) => {
+                fields.iter().any(|f| f.ty.contains("BTreeMap<"))
+            }
+            Kind::Enum(_) => false,
+        });
+
+        out.push('\n');
+
+        if uses_map {
+            out.push_str("use std::collections::BTreeMap;\n\n");
+        }
+
+        out.push_str("use serde::{Deserialize, Serialize};\n");
+
+        if !self.imports.is_empty() {
+            let imports: Vec<_> = self.imports.iter().cloned().collect();
+            writeln!(out, "\nuse crate::{{{}}};", imports.join(", ")).unwrap();
+        }
+
+        let total = self.total();
+
+        for (name, def) in &self.types {
+            out.push('\n');
+            write_doc(&mut out, "", def.doc.as_deref());
+
+            match &def.kind {
+                Kind::Struct(fields) => {
+                    let mut derives =
+                        vec!["Serialize", "Deserialize", "Debug", "Clone"];
+
+                    if total[name.as_str()] {
+                        derives.extend(&["Eq", "PartialEq", "Ord"]);
+                        derives.extend(&["PartialOrd", "Hash"]);
+                    } else {
+                        derives.push("PartialEq");
+                    }
+
+                    writeln!(out, "#[derive({})]", derives.join(", ")).unwrap();
+                    out.push_str(ARBITRARY);
+                    out.push_str(SCHEMARS);
+
+                    writeln!(out, "pub struct {} {{", name).unwrap();
+
+                    let spaced = fields.iter().any(|f| f.doc.is_some());
+
+                    for (i, field) in fields.iter().enumerate() {
+                        if spaced && i > 0 {
+                            out.push('\n');
+                        }
+
+                        write_doc(&mut out, "    ", field.doc.as_deref());
+
+                        if field.ident != field.wire {
+                            writeln!(
+                                out,
+                                "    #[serde(rename = \"{}\")]",
+                                field.wire
+                            )
+                            .unwrap();
+                        }
+
+                        writeln!(out, "    pub {}: {},", field.ident, field.ty)
+                            .unwrap();
+                    }
+
+                    out.push_str("}\n");
+                }
+                Kind::Enum(variants) => {
+                    out.push_str(
+                        "#[derive(Serialize, Deserialize, Debug, Copy, \
+                         Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]\n",
+                    );
+                    out.push_str(ARBITRARY);
+                    out.push_str(SCHEMARS);
+
+                    let rename_all = ["snake_case", "SCREAMING_SNAKE_CASE"]
+                        .iter()
+                        .find(|case| {
+                            variants
+                                .iter()
+                                .all(|v| rename(&v.ident, case) == v.wire)
+                        });
+
+                    if let Some(case) = rename_all {
+                        writeln!(out, "#[serde(rename_all = \"{}\")]", case)
+                            .unwrap();
+                    }
+
+                    writeln!(out, "pub enum {} {{", name).unwrap();
+
+                    for variant in variants {
+                        if rename_all.is_none() && variant.ident != variant.wire
+                        {
+                            writeln!(
+                                out,
+                                "    #[serde(rename = \"{}\")]",
+                                variant.wire
+                            )
+                            .unwrap();
+                        }
+
+                        writeln!(out, "    {},", variant.ident).unwrap();
+                    }
+
+                    out.push_str("}\n");
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Works out which structs can derive `Eq`, `Ord` and `Hash`. Floats
+    /// rule those out, as do fields of types that can't derive them.
+    fn total(&self) -> BTreeMap<&str, bool> {
+        let mut total: BTreeMap<&str, bool> = self
+            .types
+            .keys()
+            .map(|name| (name.as_str(), true))
+            .collect();
+
+        // Propagate until nothing changes, since types can refer to each
+        // other.
+        loop {
+            let mut changed = false;
+
+            for (name, def) in &self.types {
+                let fields = match &def.kind {
+                    Kind::Struct(fields) => fields,
+                    Kind::Enum(_) => continue,
+                };
+
+                let mut t = total[name.as_str()];
+
+                for field in fields {
+                    for word in words(&field.ty) {
+                        if word == "f64" {
+                            t = false;
+                        } else if let Some(other) = total.get(word) {
+                            t &= other;
+                        }
+                    }
+                }
+
+                if t != total[name.as_str()] {
+                    total.insert(name, t);
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return total;
+            }
+        }
+    }
+}
+
+const ARBITRARY: &str = "#[cfg_attr(feature = \"arbitrary\", \
+                         derive(proptest_derive::Arbitrary))]\n";
+
+const SCHEMARS: &str =
+    "#[cfg_attr(feature = \"schemars\", derive(schemars::JsonSchema))]\n";
+
+/// Splits a type into the paths it is made of.
+fn words(ty: &str) -> impl Iterator<Item = &str> {
+    ty.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .filter(|w| !w.is_empty())
+}
+
+fn primary_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(ty),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ => None,
+    }
+}
+
+fn format(schema: &Value) -> Option<&str> {
+    schema.get("format").and_then(Value::as_str)
+}
+
+fn has_properties(schema: &Value) -> bool {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|p| !p.is_empty())
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable").and_then(Value::as_bool) == Some(true)
+        || match schema.get("type") {
+            Some(Value::Array(types)) => {
+                types.iter().any(|t| t.as_str() == Some("null"))
+            }
+            _ => false,
+        }
+}
+
+fn description(schema: &Value) -> Option<String> {
+    schema
+        .get("description")
+        .or_else(|| schema.get("title"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(String::from)
+}
+
+/// Writes a doc comment, wrapping it to fit within 80 columns.
+fn write_doc(out: &mut String, indent: &str, doc: Option<&str>) {
+    let doc = match doc {
+        Some(doc) => doc,
+        None => return,
+    };
+
+    // rustfmt wraps comments that reach column 80.
+    let width = 79 - indent.len() - "/// ".len();
+
+    for (i, paragraph) in doc.split("\n\n").enumerate() {
+        if i > 0 {
+            writeln!(out, "{}///", indent).unwrap();
+        }
+
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.len() + 1 + word.len() > width {
+                writeln!(out, "{}/// {}", indent, line).unwrap();
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(word);
+        }
+
+        if !line.is_empty() {
+            writeln!(out, "{}/// {}", indent, line).unwrap();
+        }
+    }
+}
+
+/// Converts a schema or property name to `PascalCase`.
+fn pascal_case(name: &str) -> String {
+    let mut out = String::new();
+
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+
+            // Words in all caps are treated like any other word.
+            if word.chars().all(|c| !c.is_ascii_lowercase()) {
+                out.extend(chars.map(|c| c.to_ascii_lowercase()));
+            } else {
+                out.extend(chars);
+            }
+        }
+    }
+
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, 'N');
+    }
+
+    out
+}
+
+/// Converts a `PascalCase` identifier according to a serde `rename_all`
+/// rule.
+fn rename(ident: &str, case: &str) -> String {
+    let mut out = String::new();
+
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+
+        out.push(c.to_ascii_lowercase());
+    }
+
+    if case == "SCREAMING_SNAKE_CASE" {
+        out.to_ascii_uppercase()
+    } else {
+        out
+    }
+}
+
+fn variant_ident(wire: &str) -> String {
+    match wire {
+        "+1" => "PlusOne".to_string(),
+        "-1" => "MinusOne".to_string(),
+        _ => pascal_case(wire),
+    }
+}
+
+/// Converts a property name to a field name. Keywords are avoided the same
+/// way as in the hand-written types (e.g., `git_ref` for `ref` and
+/// `registry_type` for `type` on `PackageRegistry`).
+fn field_ident(wire: &str, type_name: &str) -> String {
+    let ident = match wire {
+        "+1" => "plus_one".to_string(),
+        "-1" => "minus_one".to_string(),
+        _ => {
+            let mut ident = String::new();
+
+            for c in wire.chars() {
+                if c.is_ascii_alphanumeric() {
+                    ident.push(c.to_ascii_lowercase());
+                } else if !ident.is_empty() && !ident.ends_with('_') {
+                    ident.push('_');
+                }
+            }
+
+            let ident = ident.trim_end_matches('_').to_string();
+
+            if ident.is_empty()
+                || ident.starts_with(|c: char| c.is_ascii_digit())
+            {
+                format!("n_{}", ident)
+            } else {
+                ident
+            }
+        }
+    };
+
+    match ident.as_str() {
+        "ref" => "git_ref".to_string(),
+        "type" => {
+            let snake = rename(type_name, "snake_case");
+            let last = snake.rsplit('_').next().unwrap_or(&snake);
+            format!("{}_type", last)
+        }
+        kw if KEYWORDS.contains(&kw) => format!("{}_", kw),
+        _ => ident,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    /// A small description that exercises the features the generator
+    /// supports. It is not a copy of GitHub's description.
+    fn spec() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "components": {
+                "schemas": {
+                    "simple-user": {
+                        "type": "object",
+                        "properties": {"login": {"type": "string"}}
+                    },
+                    "nullable-simple-user": {
+                        "type": "object",
+                        "nullable": true,
+                        "properties": {"login": {"type": "string"}}
+                    },
+                    "release-asset": {
+                        "description": "Data related to a release.",
+                        "type": "object",
+                        "properties": {
+                            "id": {"type": "integer"},
+                            "state": {
+                                "description": "State of the release asset.",
+                                "type": "string",
+                                "enum": ["uploaded", "open"]
+                            },
+                            "uploader": {
+                                "$ref":
+                                    "#/components/schemas/nullable-simple-user"
+                            },
+                            "size": {"type": "integer"},
+                            "created_at": {
+                                "type": "string",
+                                "format": "date-time"
+                            }
+                        },
+                        "required": ["id", "state", "uploader", "created_at"]
+                    },
+                    "release": {
+                        "type": "object",
+                        "properties": {
+                            "tag_name": {"type": "string"},
+                            "target_commitish": {"type": "string"},
+                            "sha": {"type": "string"},
+                            "author": {
+                                "$ref": "#/components/schemas/simple-user"
+                            },
+                            "assets": {
+                                "type": "array",
+                                "items": {
+                                    "$ref": "#/components/schemas/release-asset"
+                                }
+                            },
+                            "reactions": {
+                                "type": "object",
+                                "properties": {
+                                    "+1": {"type": "integer"},
+                                    "-1": {"type": "integer"}
+                                },
+                                "required": ["+1", "-1"]
+                            },
+                            "type": {"type": "string"},
+                            "ref": {"type": "string"},
+                            "score": {"type": "number"},
+                            "metadata": {
+                                "type": "object",
+                                "additionalProperties": {"type": "string"}
+                            },
+                            "previous": {
+                                "nullable": true,
+                                "allOf": [
+                                    {"$ref": "#/components/schemas/release"}
+                                ]
+                            },
+                            "payload": {
+                                "oneOf": [
+                                    {"type": "string"},
+                                    {"type": "integer"}
+                                ]
+                            }
+                        },
+                        "required": ["tag_name", "author", "assets", "sha"]
+                    },
+                    "visibility": {
+                        "type": "string",
+                        "enum": ["PUBLIC", "PRIVATE"]
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn structs() {
+        let out = generate(&spec(), &["release"]).unwrap();
+
+        assert!(out.contains("pub struct Release {"));
+        assert!(out.contains("pub tag_name: String,"));
+        assert!(out.contains("pub target_commitish: Option<String>,"));
+        assert!(out.contains("pub sha: Oid,"));
+        assert!(out.contains("pub assets: Vec<ReleaseAsset>,"));
+        let metadata = "pub metadata: Option<BTreeMap<String, String>>,";
+        assert!(out.contains(metadata));
+        assert!(out.contains("pub payload: Option<ClientPayload>,"));
+        assert!(
+            out.contains("use crate::{ClientPayload, DateTime, Oid, User};")
+        );
+    }
+
+    #[test]
+    fn overrides() {
+        let out = generate(&spec(), &["release"]).unwrap();
+
+        assert!(out.contains("pub author: User,"));
+        assert!(out.contains("pub uploader: Option<User>,"));
+        assert!(!out.contains("pub struct SimpleUser"));
+        assert!(out.contains("pub created_at: DateTime,"));
+    }
+
+    #[test]
+    fn recursion_is_boxed() {
+        let out = generate(&spec(), &["release"]).unwrap();
+        assert!(out.contains("pub previous: Option<Box<Release>>,"));
+    }
+
+    #[test]
+    fn names() {
+        let out = generate(&spec(), &["release"]).unwrap();
+
+        assert!(out.contains("pub struct ReleaseReactions {"));
+        assert!(out
+            .contains("    #[serde(rename = \"+1\")]\n    pub plus_one: u64,"));
+        assert!(out.contains(
+            "    #[serde(rename = \"type\")]\n    pub release_type:"
+        ));
+        assert!(
+            out.contains("    #[serde(rename = \"ref\")]\n    pub git_ref:")
+        );
+    }
+
+    #[test]
+    fn enums() {
+        let out = generate(&spec(), &["release", "visibility"]).unwrap();
+
+        assert!(out.contains(
+            "#[serde(rename_all = \"snake_case\")]\n\
+             pub enum ReleaseAssetState {\n    Uploaded,\n    Open,\n}"
+        ));
+        assert!(out.contains(
+            "#[serde(rename_all = \"SCREAMING_SNAKE_CASE\")]\n\
+             pub enum Visibility {\n    Public,\n    Private,\n}"
+        ));
+    }
+
+    #[test]
+    fn derives() {
+        let out = generate(&spec(), &["release"]).unwrap();
+
+        // `Release` contains a float.
+        assert!(out.contains(
+            "#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]\n\
+             #[cfg_attr(feature = \"arbitrary\", \
+             derive(proptest_derive::Arbitrary))]\n\
+             #[cfg_attr(feature = \"schemars\", \
+             derive(schemars::JsonSchema))]\n\
+             pub struct Release {"
+        ));
+
+        // `ReleaseAsset` doesn't.
+        assert!(out.contains(
+            "#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, \
+             Ord, PartialOrd, Hash)]\n\
+             #[cfg_attr(feature = \"arbitrary\", \
+             derive(proptest_derive::Arbitrary))]\n\
+             #[cfg_attr(feature = \"schemars\", \
+             derive(schemars::JsonSchema))]\n\
+             pub struct ReleaseAsset {"
+        ));
+    }
+
+    #[test]
+    fn unknown_schema() {
+        assert!(generate(&spec(), &["nope"]).is_err());
+    }
+}
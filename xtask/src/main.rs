@@ -0,0 +1,127 @@
+//! Development tasks for this repository. Run them with `cargo xtask <task>`.
+
+mod codegen;
+mod overrides;
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+
+const USAGE: &str = "\
+Usage: cargo xtask <task>
+
+Tasks:
+    codegen [--check] [--spec <path>]
+        Generate `src/generated.rs` from GitHub's OpenAPI description. With
+        `--check`, fail if the file is out of date instead of writing it.
+";
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("codegen") => codegen(&args[1..]),
+        _ => {
+            eprint!("{}", USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+/// Returns the root of the repository.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn codegen(args: &[String]) -> Result<(), String> {
+    let root = root();
+
+    let mut check = false;
+    let mut spec_path = root.join("openapi/api.github.com.json");
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--spec" => {
+                spec_path =
+                    args.next().ok_or("`--spec` requires a path")?.into();
+            }
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+
+    let spec = fs::read_to_string(&spec_path).map_err(|e| {
+        format!(
+            "failed to read {}: {} (see openapi/README.md)",
+            spec_path.display(),
+            e
+        )
+    })?;
+
+    let spec = serde_json::from_str(&spec).map_err(|e| {
+        format!("failed to parse {}: {}", spec_path.display(), e)
+    })?;
+
+    let code = rustfmt(&root, &codegen::generate(&spec, overrides::ROOTS)?)?;
+
+    let out_path = root.join("src/generated.rs");
+
+    if check {
+        let current = fs::read_to_string(&out_path).unwrap_or_default();
+
+        if current != code {
+            return Err(format!(
+                "{} is out of date; run `cargo xtask codegen`",
+                out_path.display()
+            ));
+        }
+    } else {
+        fs::write(&out_path, code).map_err(|e| {
+            format!("failed to write {}: {}", out_path.display(), e)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Formats the generated code with the repository's `rustfmt.toml`.
+fn rustfmt(root: &Path, code: &str) -> Result<String, String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2018"])
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(code.as_bytes())
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run rustfmt: {}", e))?;
+
+    if !output.status.success() {
+        return Err("rustfmt failed on the generated code".to_string());
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
@@ -0,0 +1,75 @@
+//! Configuration for which schemas are generated and how.
+
+/// The schemas to generate. Any schemas they refer to are generated as well,
+/// unless they are overridden below.
+pub const ROOTS: &[&str] = &[
+    "deployment",
+    "deployment-status",
+    "release",
+    "workflow-run",
+    "job",
+];
+
+/// Schemas that map onto hand-written types instead of being generated. These
+/// types have been tuned by hand (e.g., with enums or `Oid`s instead of
+/// strings) and take precedence over the description.
+pub const TYPES: &[(&str, &str)] = &[
+    ("check-run", "CheckRun"),
+    ("check-suite", "CheckSuite"),
+    ("installation", "Installation"),
+    ("integration", "App"),
+    ("issue", "Issue"),
+    ("label", "Label"),
+    ("milestone", "Milestone"),
+    ("nullable-integration", "App"),
+    ("nullable-milestone", "Milestone"),
+    ("nullable-simple-user", "User"),
+    ("private-user", "AuthenticatedUser"),
+    ("pull-request", "PullRequest"),
+    ("repository", "Repository"),
+    ("repository-ruleset", "Ruleset"),
+    ("simple-user", "User"),
+    ("team", "Team"),
+];
+
+/// Properties that are always Git object IDs when they are strings.
+pub const OIDS: &[&str] = &[
+    "after",
+    "before",
+    "commit_id",
+    "head_sha",
+    "merge_commit_sha",
+    "original_commit_id",
+    "sha",
+    "tree_id",
+];
+
+/// Properties whose type is overridden, given as `(schema, property, type)`.
+pub const PROPERTIES: &[(&str, &str, &str)] = &[
+    // These share their values with check runs.
+    ("job", "conclusion", "Conclusion"),
+    ("job", "status", "CheckRunStatus"),
+    ("workflow-run", "conclusion", "Conclusion"),
+    ("workflow-run", "status", "CheckRunStatus"),
+    // IDs that have a typed equivalent. Properties that are ambiguous within
+    // their schema (e.g., `id` in `pull-request-minimal`, which is also used
+    // for its repositories) are left as integers.
+    ("minimal-repository", "id", "RepoId"),
+    ("nullable-simple-commit", "id", "Oid"),
+    ("pull-request-minimal", "number", "IssueNumber"),
+    ("workflow-run", "check_suite_id", "CheckSuiteId"),
+    ("workflow-run", "head_repository_id", "RepoId"),
+];
+
+/// Returns the hand-written type for a schema, if any.
+pub fn type_for(schema: &str) -> Option<&'static str> {
+    TYPES.iter().find(|(s, _)| *s == schema).map(|(_, t)| *t)
+}
+
+/// Returns the overridden type for a property, if any.
+pub fn property_for(schema: &str, property: &str) -> Option<&'static str> {
+    PROPERTIES
+        .iter()
+        .find(|(s, p, _)| *s == schema && *p == property)
+        .map(|(_, _, t)| *t)
+}